@@ -109,6 +109,22 @@ fn add_assign_bound_struct_common() {
     assert_impl!(!AddAssign, X<u8>);
 }
 
+#[test]
+fn add_assign_bound_struct_attr() {
+    use std::ops::AddAssign;
+    trait MyTrait {}
+
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(AddAssign)]
+    #[add_assign(bound(T : MyTrait, ..))]
+    struct X<T>(T);
+
+    impl MyTrait for u32 {}
+
+    assert_impl!(AddAssign, X<u32>);
+    assert_impl!(!AddAssign, X<u8>);
+}
+
 #[test]
 fn add_assign_bound_field_trait() {
     use std::ops::AddAssign;
@@ -141,6 +157,85 @@ fn add_assign_bound_type() {
     assert_impl!(for<'a> AddAssign<&'a X<u32>>, X<u32>);
 }
 
+#[test]
+fn add_assign_by() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(AddAssign)]
+    struct X {
+        #[add_assign(by = |l: &mut u8, r| *l = l.wrapping_add(r))]
+        a: u8,
+        b: u32,
+    }
+
+    let mut x = X { a: 200, b: 10 };
+    x += X { a: 100, b: 20 };
+    assert_eq!(x, X { a: 44, b: 30 });
+    x += &X { a: 100, b: 20 };
+    assert_eq!(x, X { a: 144, b: 50 });
+}
+
+#[test]
+fn add_assign_by_add() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Add, AddAssign)]
+    struct X {
+        #[add(by = u8::wrapping_add)]
+        a: u8,
+        #[add(by = u8::saturating_add)]
+        b: u8,
+    }
+
+    let mut x = X { a: 200, b: 200 };
+    x += X { a: 100, b: 100 };
+    assert_eq!(x, X { a: 44, b: 255 });
+    x += &X { a: 100, b: 100 };
+    assert_eq!(x, X { a: 144, b: 255 });
+}
+
+#[test]
+fn add_assign_by_priority() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Add, AddAssign)]
+    struct X(
+        #[add(by = u8::wrapping_add)]
+        #[add_assign(by = |l: &mut u8, r| *l = l.saturating_add(r))]
+        u8,
+    );
+
+    assert_eq!(X(200) + X(100), X(44));
+    let mut x = X(200);
+    x += X(100);
+    assert_eq!(x, X(255));
+}
+
+#[test]
+fn add_assign_array() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(AddAssign)]
+    struct X([[u32; 2]; 2], (u8, [u16; 2]));
+
+    let mut x = X([[1, 2], [3, 4]], (5, [6, 7]));
+    x += X([[10, 20], [30, 40]], (50, [60, 70]));
+    assert_eq!(x, X([[11, 22], [33, 44]], (55, [66, 77])));
+    x += &X([[10, 20], [30, 40]], (50, [60, 70]));
+    assert_eq!(x, X([[21, 42], [63, 84]], (105, [126, 147])));
+}
+
+#[test]
+fn add_assign_refs() {
+    use std::ops::AddAssign;
+
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(AddAssign(refs = rhs_ref))]
+    struct X(u32);
+
+    let mut x = X(1);
+    x += &X(2);
+    assert_eq!(x, X(3));
+    assert_impl!(!AddAssign, X);
+    assert_impl!(for<'a> AddAssign<&'a X>, X);
+}
+
 #[test]
 fn sub_assign_unit_struct() {
     #[derive(Eq, PartialEq, Debug)]
@@ -100,6 +100,22 @@ fn add_bound_struct_common() {
     assert_impl!(!Add, X<u8>);
 }
 
+#[test]
+fn add_bound_struct_attr() {
+    use std::ops::Add;
+    trait MyTrait {}
+
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Add)]
+    #[add(bound(T : MyTrait, ..))]
+    struct X<T>(T);
+
+    impl MyTrait for u32 {}
+
+    assert_impl!(Add, X<u32>);
+    assert_impl!(!Add, X<u8>);
+}
+
 #[test]
 fn add_bound_field_trait() {
     use std::ops::Add;
@@ -146,6 +162,239 @@ fn add_bound_field_common() {
     assert_impl!(!Add, X<u8>);
 }
 
+#[test]
+fn add_by() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Add)]
+    struct X {
+        #[add(by = u8::wrapping_add)]
+        a: u8,
+        #[add(by = u8::saturating_add)]
+        b: u8,
+        c: u32,
+    }
+
+    fn x(a: u8, b: u8, c: u32) -> X {
+        X { a, b, c }
+    }
+
+    assert_eq!(x(200, 200, 10) + x(100, 100, 20), x(44, 255, 30));
+    assert_eq!(&x(200, 200, 10) + x(100, 100, 20), x(44, 255, 30));
+    assert_eq!(x(200, 200, 10) + &x(100, 100, 20), x(44, 255, 30));
+    assert_eq!(&x(200, 200, 10) + &x(100, 100, 20), x(44, 255, 30));
+}
+
+#[test]
+fn add_by_closure() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Add)]
+    struct X(#[add(by = |l, r| l * 10 + r)] u32);
+
+    assert_eq!(X(1) + X(2), X(12));
+    assert_eq!(&X(1) + &X(2), X(12));
+}
+
+#[test]
+fn add_by_generics() {
+    use std::ops::Add;
+
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Add)]
+    struct X<T>(#[add(by = |l, _| l)] T);
+
+    #[derive(Eq, PartialEq, Debug)]
+    struct NoClone;
+
+    assert_eq!(X(1) + X(2), X(1));
+    assert_eq!(&X(1) + &X(2), X(1));
+    assert_eq!(X(NoClone) + X(NoClone), X(NoClone));
+    assert_impl!(Add, X<NoClone>);
+    assert_impl!(!Add, &X<NoClone>);
+}
+
+#[test]
+fn add_by_bound() {
+    use std::ops::Add;
+    trait MyTrait {}
+
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Add)]
+    struct X<T>(#[add(by = |l, _| l, bound(T : MyTrait, ..))] T);
+
+    impl MyTrait for u32 {}
+
+    assert_impl!(Add, X<u32>);
+    assert_impl!(!Add, X<u8>);
+}
+
+#[test]
+fn add_array() {
+    #[derive(PartialEq, Debug)]
+    #[derive_ex(Add)]
+    struct Color {
+        rgba: [f32; 4],
+    }
+
+    let l = Color {
+        rgba: [0.5, 0.25, 0.0, 1.0],
+    };
+    let r = Color {
+        rgba: [0.25, 0.5, 1.0, 0.0],
+    };
+    let e = Color {
+        rgba: [0.75, 0.75, 1.0, 1.0],
+    };
+    assert_eq!(&l + &r, e);
+    assert_eq!(&l + Color { rgba: r.rgba }, e);
+    assert_eq!(Color { rgba: l.rgba } + &r, e);
+    assert_eq!(l + r, e);
+}
+
+#[test]
+fn add_array_not_copy() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Add)]
+    struct X([X2; 2]);
+
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Add)]
+    struct X2(u32);
+
+    assert_eq!(X([X2(1), X2(2)]) + X([X2(10), X2(20)]), X([X2(11), X2(22)]));
+    assert_eq!(
+        &X([X2(1), X2(2)]) + &X([X2(10), X2(20)]),
+        X([X2(11), X2(22)])
+    );
+}
+
+#[test]
+fn add_nested_array() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Add)]
+    struct Matrix([[i32; 2]; 2]);
+
+    assert_eq!(
+        Matrix([[1, 2], [3, 4]]) + Matrix([[10, 20], [30, 40]]),
+        Matrix([[11, 22], [33, 44]])
+    );
+    assert_eq!(
+        &Matrix([[1, 2], [3, 4]]) + &Matrix([[10, 20], [30, 40]]),
+        Matrix([[11, 22], [33, 44]])
+    );
+}
+
+#[test]
+fn add_tuple() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Add)]
+    struct X((u32, u8), [(u8, u16); 2], ());
+
+    assert_eq!(
+        X((1, 2), [(3, 4), (5, 6)], ()) + X((10, 20), [(30, 40), (50, 60)], ()),
+        X((11, 22), [(33, 44), (55, 66)], ())
+    );
+    assert_eq!(
+        &X((1, 2), [(3, 4), (5, 6)], ()) + &X((10, 20), [(30, 40), (50, 60)], ()),
+        X((11, 22), [(33, 44), (55, 66)], ())
+    );
+}
+
+#[test]
+fn add_array_generics() {
+    use std::ops::Add;
+
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Add)]
+    struct X<T, const N: usize>([T; N]);
+
+    #[derive(Eq, PartialEq, Debug)]
+    struct NoAdd;
+
+    assert_eq!(X([1, 2, 3]) + X([10, 20, 30]), X([11, 22, 33]));
+    assert_eq!(&X([1, 2]) + &X([10, 20]), X([11, 22]));
+    assert_impl!(Add, X<u32, 4>, &X<u32, 4>);
+    assert_impl!(!Add, X<NoAdd, 4>, &X<NoAdd, 4>);
+}
+
+#[test]
+fn add_refs_owned() {
+    use std::ops::Add;
+
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Add(refs = owned))]
+    struct X(u32);
+
+    assert_eq!(X(1) + X(2), X(3));
+    assert_impl!(Add<X>, X);
+    assert_impl!(!Add<X>, &X);
+    assert_impl!(for<'a> !Add<&'a X>, X, &X);
+}
+
+#[test]
+fn add_refs_list() {
+    use std::ops::Add;
+
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Add(refs = [owned, both_ref]))]
+    struct X(u32);
+
+    assert_eq!(X(1) + X(2), X(3));
+    assert_eq!(&X(1) + &X(2), X(3));
+    assert_impl!(Add<X>, X);
+    assert_impl!(!Add<X>, &X);
+    assert_impl!(for<'a> Add<&'a X>, &X);
+    assert_impl!(for<'a> !Add<&'a X>, X);
+}
+
+#[test]
+fn add_refs_lhs_rhs() {
+    use std::ops::Add;
+
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Add(refs = [lhs_ref, rhs_ref]))]
+    struct X(u32);
+
+    assert_eq!(&X(1) + X(2), X(3));
+    assert_eq!(X(1) + &X(2), X(3));
+    assert_impl!(!Add<X>, X);
+    assert_impl!(Add<X>, &X);
+    assert_impl!(for<'a> Add<&'a X>, X);
+    assert_impl!(for<'a> !Add<&'a X>, &X);
+}
+
+#[test]
+fn add_refs_common() {
+    use std::ops::{Add, AddAssign};
+
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Add, AddAssign, refs = [owned, lhs_ref])]
+    struct X(u32);
+
+    assert_impl!(Add<X>, X, &X);
+    assert_impl!(for<'a> !Add<&'a X>, X, &X);
+    assert_impl!(AddAssign<X>, X);
+    assert_impl!(for<'a> !AddAssign<&'a X>, X);
+}
+
+#[test]
+fn add_refs_not_clone_field() {
+    #[derive(Eq, PartialEq, Debug)]
+    struct NoRefAdd(u32);
+
+    impl std::ops::Add for NoRefAdd {
+        type Output = Self;
+        fn add(self, rhs: Self) -> Self {
+            NoRefAdd(self.0 + rhs.0)
+        }
+    }
+
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Add(refs = owned))]
+    struct X(NoRefAdd);
+
+    assert_eq!(X(NoRefAdd(1)) + X(NoRefAdd(2)), X(NoRefAdd(3)));
+}
+
 #[test]
 fn sub_unit_struct() {
     #[derive_ex(Sub)]
@@ -0,0 +1,5 @@
+fn main() {}
+
+#[derive(derive_ex::Ex)]
+#[derive_ex(Add(refs = []))]
+struct X(u32);
@@ -0,0 +1,8 @@
+fn main() {}
+
+#[derive(derive_ex::Ex)]
+#[derive_ex(Debug)]
+struct X {
+    #[debug(limit = 3, display)]
+    field: Vec<u32>,
+}
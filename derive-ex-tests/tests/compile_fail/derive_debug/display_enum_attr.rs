@@ -0,0 +1,16 @@
+fn main() {}
+
+#[derive(derive_ex::Ex)]
+#[derive_ex(Debug(display))]
+enum X {
+    #[debug(transparent)]
+    A(u32),
+}
+
+impl std::fmt::Display for X {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            X::A(a) => write!(f, "{a}"),
+        }
+    }
+}
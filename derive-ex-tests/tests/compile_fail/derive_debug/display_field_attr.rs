@@ -0,0 +1,14 @@
+fn main() {}
+
+#[derive(derive_ex::Ex)]
+#[derive_ex(Debug(display))]
+struct X {
+    #[debug(rename = "b")]
+    a: u32,
+}
+
+impl std::fmt::Display for X {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.a)
+    }
+}
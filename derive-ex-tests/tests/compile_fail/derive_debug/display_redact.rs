@@ -0,0 +1,12 @@
+fn main() {}
+
+#[derive(derive_ex::Ex)]
+#[derive_ex(Debug(display))]
+#[debug(redact)]
+struct Secret(String);
+
+impl std::fmt::Display for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,14 @@
+fn main() {}
+
+#[derive(derive_ex::Ex)]
+#[derive_ex(Debug(display))]
+#[debug(rename = "V")]
+union U {
+    a: u32,
+}
+
+impl std::fmt::Display for U {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", unsafe { self.a })
+    }
+}
@@ -0,0 +1,15 @@
+use derive_ex::derive_ex;
+
+#[derive_ex(Debug)]
+struct Inner {
+    b: u32,
+}
+
+#[derive_ex(Debug)]
+struct X {
+    a: u32,
+    #[debug(flatten)]
+    inner: Inner,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use derive_ex::derive_ex;
+
+#[derive_ex(Debug)]
+#[debug(flattenable)]
+struct Inner {
+    b: u32,
+}
+
+#[derive_ex(Debug)]
+struct X {
+    #[debug(flatten, redact)]
+    inner: Inner,
+}
+
+fn main() {}
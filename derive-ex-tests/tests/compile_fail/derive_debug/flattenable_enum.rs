@@ -0,0 +1,9 @@
+use derive_ex::derive_ex;
+
+#[derive_ex(Debug)]
+#[debug(flattenable)]
+enum X {
+    A { a: u32 },
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+fn main() {}
+
+#[derive(derive_ex::Ex)]
+#[derive_ex(Debug)]
+#[debug(redact)]
+struct X {
+    #[debug(format = "<{}>")]
+    user: String,
+    password: String,
+}
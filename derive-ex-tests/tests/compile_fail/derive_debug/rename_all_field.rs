@@ -0,0 +1,8 @@
+fn main() {}
+
+#[derive(derive_ex::Ex)]
+#[derive_ex(Debug)]
+struct X {
+    #[debug(rename_all = "camelCase")]
+    field: u32,
+}
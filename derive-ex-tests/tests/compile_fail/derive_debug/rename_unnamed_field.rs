@@ -0,0 +1,5 @@
+fn main() {}
+
+#[derive(derive_ex::Ex)]
+#[derive_ex(Debug)]
+struct X(#[debug(rename = "a")] u32);
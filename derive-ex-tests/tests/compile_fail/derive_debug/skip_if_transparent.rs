@@ -0,0 +1,5 @@
+fn main() {}
+
+#[derive(derive_ex::Ex)]
+#[derive_ex(Debug)]
+struct X(#[debug(transparent, skip_if = Option::is_none)] Option<u32>);
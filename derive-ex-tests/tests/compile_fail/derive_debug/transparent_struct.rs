@@ -0,0 +1,8 @@
+fn main() {}
+
+#[derive(derive_ex::Ex)]
+#[derive_ex(Debug)]
+#[debug(transparent)]
+struct X {
+    a: u32,
+}
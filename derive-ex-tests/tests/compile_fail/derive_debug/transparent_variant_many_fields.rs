@@ -0,0 +1,8 @@
+fn main() {}
+
+#[derive(derive_ex::Ex)]
+#[derive_ex(Debug)]
+enum X {
+    #[debug(transparent)]
+    A(u32, u32),
+}
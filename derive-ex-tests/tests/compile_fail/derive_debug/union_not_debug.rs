@@ -0,0 +1,7 @@
+fn main() {}
+
+#[derive(derive_ex::Ex)]
+#[derive_ex(Clone)]
+union X {
+    a: u32,
+}
@@ -0,0 +1,8 @@
+fn main() {}
+
+#[derive(derive_ex::Ex)]
+#[derive_ex(Debug)]
+#[debug(rename_all = "Title Case")]
+struct X {
+    field: u32,
+}
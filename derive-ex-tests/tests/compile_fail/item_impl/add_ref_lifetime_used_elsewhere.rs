@@ -0,0 +1,13 @@
+fn main() {}
+
+use std::ops::Add;
+
+struct X<'a>(&'a u32);
+
+#[derive_ex::derive_ex(Add)]
+impl<'a> Add<&'a X<'a>> for &'a X<'a> {
+    type Output = u32;
+    fn add(self, rhs: &'a X<'a>) -> Self::Output {
+        self.0 + rhs.0
+    }
+}
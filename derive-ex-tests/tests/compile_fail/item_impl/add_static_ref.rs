@@ -0,0 +1,13 @@
+fn main() {}
+
+use std::ops::Add;
+
+struct X(u32);
+
+#[derive_ex::derive_ex(Add)]
+impl Add<&'static X> for &'static X {
+    type Output = X;
+    fn add(self, rhs: &'static X) -> Self::Output {
+        X(self.0 + rhs.0)
+    }
+}
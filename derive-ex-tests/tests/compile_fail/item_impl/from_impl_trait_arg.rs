@@ -0,0 +1,10 @@
+fn main() {}
+
+struct X(String);
+
+#[derive_ex::derive_ex(From)]
+impl X {
+    fn new(value: impl Into<String>) -> Self {
+        X(value.into())
+    }
+}
@@ -0,0 +1,10 @@
+fn main() {}
+
+struct X(u32);
+
+#[derive_ex::derive_ex(From)]
+impl X {
+    fn new(value: u32) -> Option<Self> {
+        Some(X(value))
+    }
+}
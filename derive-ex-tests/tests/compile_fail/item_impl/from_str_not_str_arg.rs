@@ -0,0 +1,10 @@
+fn main() {}
+
+struct X(u32);
+
+#[derive_ex::derive_ex(FromStr)]
+impl X {
+    fn parse(s: String) -> Result<Self, std::num::ParseIntError> {
+        Ok(X(s.parse()?))
+    }
+}
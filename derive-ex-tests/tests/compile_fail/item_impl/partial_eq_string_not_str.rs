@@ -0,0 +1,10 @@
+fn main() {}
+
+struct X(u32);
+
+#[derive_ex::derive_ex(PartialEq(string))]
+impl PartialEq<u32> for X {
+    fn eq(&self, other: &u32) -> bool {
+        self.0 == *other
+    }
+}
@@ -1,5 +1,5 @@
 use derive_ex::derive_ex;
-use derive_ex_tests::assert_debug_eq;
+use derive_ex_tests::{assert_debug_eq, assert_impl};
 
 #[test]
 fn unit() {
@@ -96,7 +96,7 @@ fn skip() {
         impl std::fmt::Debug for X {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 match self {
-                    X::A { y, .. } => f.debug_struct("A").field("y", y).finish(),
+                    X::A { y, .. } => f.debug_struct("A").field("y", y).finish_non_exhaustive(),
                     X::B { .. } => unreachable!(),
                 }
             }
@@ -120,3 +120,321 @@ fn transparent() {
     let e = 1;
     assert_debug_eq(a, e);
 }
+
+#[test]
+#[allow(dead_code)]
+fn with_and_format() {
+    fn fmt_hex(value: &u32, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "0x{value:x}")
+    }
+    #[allow(unused)]
+    #[derive_ex(Debug)]
+    enum X {
+        A(#[debug(with = fmt_hex)] u32),
+        B {
+            #[debug(format = "{:#x}")]
+            x: u32,
+            y: u32,
+        },
+    }
+    assert_eq!(format!("{:?}", X::A(255)), "A(0xff)");
+    assert_eq!(
+        format!("{:?}", X::B { x: 255, y: 1 }),
+        "B { x: 0xff, y: 1 }"
+    );
+}
+
+#[test]
+#[allow(dead_code)]
+fn rename() {
+    #[derive_ex(Debug)]
+    enum X {
+        #[debug(rename = "a")]
+        A,
+        B {
+            #[debug(rename = "kind")]
+            r#type: u32,
+        },
+    }
+    assert_eq!(format!("{:?}", X::A), "a");
+    assert_eq!(format!("{:?}", X::B { r#type: 1 }), "B { kind: 1 }");
+}
+
+#[test]
+#[allow(dead_code)]
+fn rename_all() {
+    #[derive_ex(Debug)]
+    #[debug(rename_all = "snake_case")]
+    enum X {
+        HttpRequest,
+        #[debug(rename = "resp")]
+        HttpResponse,
+        IOError(u32),
+    }
+    assert_eq!(format!("{:?}", X::HttpRequest), "http_request");
+    assert_eq!(format!("{:?}", X::HttpResponse), "resp");
+    assert_eq!(format!("{:?}", X::IOError(1)), "io_error(1)");
+}
+
+#[test]
+#[allow(dead_code)]
+fn rename_all_applies_only_to_variants() {
+    #[derive_ex(Debug)]
+    #[debug(rename_all = "camelCase")]
+    enum X {
+        UserInfo { user_id: u32 },
+    }
+    assert_eq!(
+        format!("{:?}", X::UserInfo { user_id: 1 }),
+        "userInfo { user_id: 1 }"
+    );
+}
+
+#[test]
+#[allow(dead_code)]
+fn skip_if() {
+    #[derive_ex(Debug)]
+    enum X {
+        A {
+            #[debug(skip_if = Option::is_none)]
+            a: Option<u32>,
+        },
+        B(#[debug(skip_if = Option::is_none)] Option<u32>),
+    }
+    assert_eq!(format!("{:?}", X::A { a: None }), "A");
+    assert_eq!(format!("{:?}", X::A { a: Some(1) }), "A { a: Some(1) }");
+    assert_eq!(format!("{:?}", X::B(None)), "B");
+    assert_eq!(format!("{:?}", X::B(Some(1))), "B(Some(1))");
+}
+
+#[test]
+#[allow(dead_code)]
+fn redact() {
+    #[derive_ex(Debug)]
+    #[debug(redact)]
+    enum X {
+        A(u32),
+        #[debug(redact = false)]
+        B(u32, #[debug(redact)] u32),
+    }
+    assert_eq!(format!("{:?}", X::A(1)), "A(***)");
+    assert_eq!(format!("{:?}", X::B(1, 2)), "B(1, ***)");
+}
+
+#[test]
+#[allow(dead_code)]
+fn non_exhaustive() {
+    #[derive_ex(Debug)]
+    enum X {
+        #[non_exhaustive]
+        A {
+            x: u32,
+        },
+        #[non_exhaustive]
+        #[debug(non_exhaustive = false)]
+        B {
+            x: u32,
+        },
+        C(#[debug(skip)] u32, u32),
+        #[non_exhaustive]
+        D,
+    }
+    assert_eq!(format!("{:?}", X::A { x: 1 }), "A { x: 1, .. }");
+    assert_eq!(format!("{:?}", X::B { x: 1 }), "B { x: 1 }");
+    assert_eq!(format!("{:?}", X::C(1, 2)), "C(2, ..)");
+    assert_eq!(format!("{:?}", X::D), "D");
+}
+
+#[test]
+#[allow(dead_code)]
+fn non_exhaustive_for_enum() {
+    #[derive_ex(Debug)]
+    #[debug(non_exhaustive = true)]
+    enum X {
+        A {
+            x: u32,
+        },
+        #[debug(non_exhaustive = false)]
+        B {
+            x: u32,
+        },
+    }
+    assert_eq!(format!("{:?}", X::A { x: 1 }), "A { x: 1, .. }");
+    assert_eq!(format!("{:?}", X::B { x: 1 }), "B { x: 1 }");
+}
+
+#[test]
+#[allow(dead_code)]
+fn flatten() {
+    #[derive_ex(Debug)]
+    #[debug(flattenable)]
+    struct Inner {
+        b: u32,
+    }
+
+    #[derive_ex(Debug)]
+    enum X {
+        A {
+            a: u32,
+            #[debug(flatten)]
+            inner: Inner,
+        },
+    }
+    let a = X::A {
+        a: 1,
+        inner: Inner { b: 2 },
+    };
+    assert_eq!(format!("{a:?}"), "A { a: 1, b: 2 }");
+}
+
+#[test]
+#[allow(dead_code)]
+fn transparent_variant() {
+    #[derive_ex(Debug)]
+    enum Value {
+        #[debug(transparent)]
+        Int(i32),
+        Str(String),
+    }
+    assert_eq!(format!("{:?}", Value::Int(3)), "3");
+    assert_eq!(format!("{:?}", Value::Str("a".into())), r#"Str("a")"#);
+}
+
+#[test]
+#[allow(dead_code)]
+fn transparent_variants() {
+    #[derive_ex(Debug)]
+    #[debug(transparent_variants)]
+    enum Value {
+        Int(i32),
+        Str { value: String },
+        Pair(i32, i32),
+        Null,
+    }
+    assert_eq!(format!("{:?}", Value::Int(3)), "3");
+    assert_eq!(format!("{:?}", Value::Str { value: "a".into() }), r#""a""#);
+    assert_eq!(format!("{:?}", Value::Pair(1, 2)), "Pair(1, 2)");
+    assert_eq!(format!("{:?}", Value::Null), "Null");
+}
+
+#[test]
+#[allow(dead_code)]
+fn transparent_variants_skip() {
+    #[derive_ex(Debug)]
+    #[debug(transparent_variants)]
+    enum Value {
+        Int(#[debug(skip)] u32, i32),
+        Str {
+            value: String,
+            #[debug(skip)]
+            len: usize,
+        },
+        Null(#[debug(skip)] u32),
+    }
+    assert_eq!(format!("{:?}", Value::Int(1, 3)), "3");
+    assert_eq!(
+        format!(
+            "{:?}",
+            Value::Str {
+                value: "a".into(),
+                len: 1
+            }
+        ),
+        r#""a""#
+    );
+    assert_eq!(format!("{:?}", Value::Null(1)), "Null(..)");
+}
+
+#[test]
+#[allow(dead_code)]
+fn transparent_variant_skip() {
+    #[derive_ex(Debug)]
+    enum Value {
+        #[debug(transparent)]
+        Int(#[debug(skip)] u32, i32),
+    }
+    assert_eq!(format!("{:?}", Value::Int(1, 3)), "3");
+}
+
+#[test]
+#[allow(dead_code)]
+fn generics_transparent_variants() {
+    use std::fmt::Debug;
+
+    #[derive_ex(Debug)]
+    #[debug(transparent_variants)]
+    enum X<T> {
+        A(T),
+    }
+
+    struct NoDebug;
+
+    assert_impl!(Debug, X<u32>);
+    assert_impl!(!Debug, X<NoDebug>);
+}
+
+#[test]
+#[allow(dead_code)]
+fn variant_name_only() {
+    #[derive_ex(Debug)]
+    #[debug(variant_name_only, rename_all = "snake_case")]
+    enum X {
+        HttpRequest {
+            id: u32,
+        },
+        #[debug(rename = "resp")]
+        HttpResponse(u32),
+        Closed,
+    }
+    assert_eq!(format!("{:?}", X::HttpRequest { id: 1 }), "http_request");
+    assert_eq!(format!("{:?}", X::HttpResponse(1)), "resp");
+    assert_eq!(format!("{:?}", X::Closed), "closed");
+}
+
+#[test]
+#[allow(dead_code)]
+fn generics_variant_name_only() {
+    use std::fmt::Debug;
+
+    #[derive_ex(Debug)]
+    #[debug(variant_name_only)]
+    enum X<T> {
+        A(T),
+    }
+
+    struct NoDebug;
+
+    assert_impl!(Debug, X<NoDebug>);
+}
+
+#[test]
+fn display() {
+    use std::fmt::{Display, Formatter, Result};
+
+    #[derive_ex(Debug(display))]
+    enum X {
+        A,
+        B(u32),
+    }
+
+    impl Display for X {
+        fn fmt(&self, f: &mut Formatter) -> Result {
+            match self {
+                X::A => write!(f, "a"),
+                X::B(x) => write!(f, "b{x}"),
+            }
+        }
+    }
+    assert_eq!(format!("{:?}", X::A), "a");
+    assert_eq!(format!("{:?}", X::B(1)), "b1");
+}
+
+#[test]
+#[allow(dead_code)]
+fn display_field() {
+    #[derive_ex(Debug)]
+    enum X {
+        A(#[debug(display)] String),
+    }
+    assert_eq!(format!("{:?}", X::A("a".into())), "A(a)");
+}
@@ -74,7 +74,9 @@ fn skip() {
         }
         impl std::fmt::Debug for X {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                f.debug_struct("X").field("a", &self.a).finish()
+                f.debug_struct("X")
+                    .field("a", &self.a)
+                    .finish_non_exhaustive()
             }
         }
         X { a: 1, b: 2 }
@@ -94,6 +96,39 @@ fn non_exhaustive() {
         }
         X { a: 1, b: 2 }
     };
+    let e = {
+        #[allow(unused)]
+        #[non_exhaustive]
+        struct X {
+            a: u32,
+            b: u32,
+        }
+        impl std::fmt::Debug for X {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct("X")
+                    .field("a", &self.a)
+                    .field("b", &self.b)
+                    .finish_non_exhaustive()
+            }
+        }
+        X { a: 1, b: 2 }
+    };
+    assert_debug_eq(a, e);
+}
+
+#[test]
+#[allow(dead_code)]
+fn non_exhaustive_false() {
+    let a = {
+        #[derive_ex(Debug)]
+        #[debug(non_exhaustive = false)]
+        #[non_exhaustive]
+        struct X {
+            a: u32,
+            b: u32,
+        }
+        X { a: 1, b: 2 }
+    };
     let e = {
         #[allow(unused)]
         #[derive(Debug)]
@@ -107,6 +142,46 @@ fn non_exhaustive() {
     assert_debug_eq(a, e);
 }
 
+#[test]
+fn non_exhaustive_unit() {
+    let a = {
+        #[derive_ex(Debug)]
+        #[non_exhaustive]
+        struct X;
+        X
+    };
+    let e = {
+        #[derive(Debug)]
+        #[non_exhaustive]
+        struct X;
+        X
+    };
+    assert_debug_eq(a, e);
+}
+
+#[test]
+#[allow(dead_code)]
+fn non_exhaustive_true() {
+    #[derive_ex(Debug)]
+    #[debug(non_exhaustive = true)]
+    struct X(u32);
+    assert_eq!(format!("{:?}", X(1)), "X(1, ..)");
+    assert_eq!(format!("{:#?}", X(1)), "X(\n    1,\n    ..,\n)");
+}
+
+#[test]
+#[allow(dead_code)]
+fn skip_non_exhaustive_false() {
+    #[derive_ex(Debug)]
+    #[debug(non_exhaustive = false)]
+    struct X {
+        a: u32,
+        #[debug(skip)]
+        b: u32,
+    }
+    assert_eq!(format!("{:?}", X { a: 1, b: 2 }), "X { a: 1 }");
+}
+
 #[test]
 fn transparent_field() {
     #[derive_ex(Debug)]
@@ -223,7 +298,9 @@ fn derive_macro() {
         }
         impl std::fmt::Debug for X {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                f.debug_struct("X").field("a", &self.a).finish()
+                f.debug_struct("X")
+                    .field("a", &self.a)
+                    .finish_non_exhaustive()
             }
         }
         X { a: 1, b: 2 }
@@ -251,10 +328,758 @@ fn ignore_compatibility() {
         }
         impl std::fmt::Debug for X {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                f.debug_struct("X").field("a", &self.a).finish()
+                f.debug_struct("X")
+                    .field("a", &self.a)
+                    .finish_non_exhaustive()
             }
         }
         X { a: 1, b: 2 }
     };
     assert_debug_eq(a, e);
 }
+
+#[test]
+#[allow(dead_code)]
+fn with() {
+    fn fmt_hex(value: &u32, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "0x{value:x}")
+    }
+    let a = {
+        #[allow(unused)]
+        #[derive_ex(Debug)]
+        struct X {
+            a: u32,
+            #[debug(with = fmt_hex)]
+            b: u32,
+        }
+        X { a: 1, b: 255 }
+    };
+    let e = "X { a: 1, b: 0xff }";
+    assert_eq!(format!("{a:?}"), e);
+}
+
+#[test]
+#[allow(dead_code)]
+fn with_user_fn_named_like_generated_items() {
+    fn debug_fn(value: &u32, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "0x{value:x}")
+    }
+    fn with(value: &u32, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "<{value}>")
+    }
+    fn debug(value: &Option<u32>) -> bool {
+        value.is_none()
+    }
+    fn skip_if(value: &Option<u32>) -> bool {
+        value.is_none()
+    }
+
+    #[derive_ex(Debug)]
+    struct X {
+        #[debug(with = debug_fn)]
+        a: u32,
+        #[debug(with = with)]
+        b: u32,
+        #[debug(skip_if = debug)]
+        c: Option<u32>,
+        #[debug(skip_if = skip_if)]
+        d: Option<u32>,
+    }
+    let a = X {
+        a: 255,
+        b: 1,
+        c: None,
+        d: Some(2),
+    };
+    assert_eq!(format!("{a:?}"), "X { a: 0xff, b: <1>, d: Some(2) }");
+}
+
+#[test]
+fn with_closure() {
+    let a = {
+        #[derive_ex(Debug)]
+        struct X(
+            #[allow(unused)]
+            #[debug(with = |v: &Vec<u32>, f: &mut std::fmt::Formatter| write!(f, "len={}", v.len()))]
+            Vec<u32>,
+        );
+        X(vec![1, 2, 3])
+    };
+    assert_eq!(format!("{a:?}"), "X(len=3)");
+}
+
+#[test]
+fn format() {
+    let a = {
+        #[allow(unused)]
+        #[derive_ex(Debug)]
+        struct X {
+            #[debug(format = "{:#x}")]
+            a: u32,
+            #[debug(format = "{}")]
+            b: String,
+        }
+        X {
+            a: 255,
+            b: "abc".into(),
+        }
+    };
+    let e = "X { a: 0xff, b: abc }";
+    assert_eq!(format!("{a:?}"), e);
+}
+
+#[test]
+#[allow(dead_code)]
+fn format_transparent() {
+    let a = {
+        #[derive_ex(Debug)]
+        struct X(#[debug(transparent, format = "<{}>")] u32);
+        X(1)
+    };
+    assert_eq!(format!("{a:?}"), "<1>");
+}
+
+#[test]
+#[allow(dead_code)]
+fn generics_with() {
+    use std::fmt::{Debug, Display};
+
+    fn fmt_display<T: Display>(value: &T, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{value}")
+    }
+
+    #[derive_ex(Debug)]
+    struct X<T>(#[debug(with = fmt_display, bound(T : Display))] T);
+
+    #[derive_ex(Debug)]
+    struct Y<T>(#[debug(format = "{}", bound(T : Display))] T);
+
+    struct NoDisplay;
+
+    assert_impl!(Debug, X<u32>);
+    assert_impl!(!Debug, X<NoDisplay>);
+    assert_impl!(Debug, Y<u32>);
+    assert_impl!(!Debug, Y<NoDisplay>);
+}
+
+#[test]
+#[allow(dead_code)]
+fn generics_with_no_bound() {
+    use std::fmt::Debug;
+
+    fn fmt_ignore<T>(_: &T, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "_")
+    }
+
+    #[derive_ex(Debug)]
+    struct X<T>(#[debug(with = fmt_ignore)] T);
+
+    struct NoDebug;
+
+    assert_impl!(Debug, X<NoDebug>);
+}
+
+#[test]
+#[allow(dead_code)]
+fn raw_ident() {
+    let a = {
+        #[derive_ex(Debug)]
+        struct X {
+            r#type: u32,
+        }
+        X { r#type: 1 }
+    };
+    let e = {
+        #[derive(Debug)]
+        struct X {
+            r#type: u32,
+        }
+        X { r#type: 1 }
+    };
+    assert_debug_eq(a, e);
+}
+
+#[test]
+#[allow(dead_code)]
+fn rename() {
+    let a = {
+        #[derive_ex(Debug)]
+        #[debug(rename = "Y")]
+        struct X {
+            #[debug(rename = "kind")]
+            r#type: u32,
+            b: u32,
+        }
+        X { r#type: 1, b: 2 }
+    };
+    assert_eq!(format!("{a:?}"), "Y { kind: 1, b: 2 }");
+}
+
+#[test]
+#[allow(dead_code)]
+fn rename_tuple() {
+    let a = {
+        #[derive_ex(Debug)]
+        #[debug(rename = "Y")]
+        struct X(u32);
+        X(1)
+    };
+    assert_eq!(format!("{a:?}"), "Y(1)");
+}
+
+#[test]
+#[allow(dead_code)]
+fn rename_all() {
+    let a = {
+        #[derive_ex(Debug)]
+        #[debug(rename_all = "camelCase")]
+        struct X {
+            user_id: u32,
+            #[debug(rename = "NAME")]
+            user_name: u32,
+            r#type: u32,
+        }
+        X {
+            user_id: 1,
+            user_name: 2,
+            r#type: 3,
+        }
+    };
+    assert_eq!(format!("{a:?}"), "X { userId: 1, NAME: 2, type: 3 }");
+}
+
+#[test]
+#[allow(dead_code)]
+fn rename_all_rules() {
+    macro_rules! check {
+        ($rule:literal, $e:literal) => {{
+            #[derive_ex(Debug)]
+            #[debug(rename_all = $rule)]
+            struct X {
+                http_status_code: u32,
+                http2_url: u32,
+            }
+            assert_eq!(
+                format!(
+                    "{:?}",
+                    X {
+                        http_status_code: 1,
+                        http2_url: 2,
+                    }
+                ),
+                $e
+            );
+        }};
+    }
+    check!("lowercase", "X { http_status_code: 1, http2_url: 2 }");
+    check!("UPPERCASE", "X { HTTP_STATUS_CODE: 1, HTTP2_URL: 2 }");
+    check!("PascalCase", "X { HttpStatusCode: 1, Http2Url: 2 }");
+    check!("camelCase", "X { httpStatusCode: 1, http2Url: 2 }");
+    check!("snake_case", "X { http_status_code: 1, http2_url: 2 }");
+    check!(
+        "SCREAMING_SNAKE_CASE",
+        "X { HTTP_STATUS_CODE: 1, HTTP2_URL: 2 }"
+    );
+    check!("kebab-case", "X { http-status-code: 1, http2-url: 2 }");
+    check!(
+        "SCREAMING-KEBAB-CASE",
+        "X { HTTP-STATUS-CODE: 1, HTTP2-URL: 2 }"
+    );
+}
+
+#[test]
+#[allow(dead_code)]
+fn skip_if() {
+    #[derive_ex(Debug)]
+    struct X {
+        a: u32,
+        #[debug(skip_if = Option::is_none)]
+        b: Option<u32>,
+        #[debug(skip_if = Vec::is_empty)]
+        c: Vec<u32>,
+    }
+    let a = X {
+        a: 1,
+        b: None,
+        c: vec![],
+    };
+    assert_eq!(format!("{a:?}"), "X { a: 1 }");
+    let a = X {
+        a: 1,
+        b: Some(2),
+        c: vec![3],
+    };
+    assert_eq!(format!("{a:?}"), "X { a: 1, b: Some(2), c: [3] }");
+}
+
+#[test]
+#[allow(dead_code)]
+fn skip_if_tuple() {
+    #[derive_ex(Debug)]
+    struct X(u32, #[debug(skip_if = |x: &u32| *x == 0)] u32);
+    assert_eq!(format!("{:?}", X(1, 0)), "X(1)");
+    assert_eq!(format!("{:?}", X(1, 2)), "X(1, 2)");
+}
+
+#[test]
+#[allow(dead_code)]
+fn skip_if_with_transparent_sibling() {
+    #[derive_ex(Debug)]
+    struct X {
+        #[debug(skip_if = Option::is_none)]
+        a: Option<u32>,
+        #[debug(transparent)]
+        b: u32,
+    }
+    assert_eq!(format!("{:?}", X { a: None, b: 2 }), "2");
+    assert_eq!(format!("{:?}", X { a: Some(1), b: 2 }), "2");
+}
+
+#[test]
+#[allow(dead_code)]
+fn skip_if_with_format() {
+    #[derive_ex(Debug)]
+    struct X {
+        #[debug(skip_if = Option::is_none, format = "{:?}!")]
+        a: Option<u32>,
+    }
+    assert_eq!(format!("{:?}", X { a: None }), "X");
+    assert_eq!(format!("{:?}", X { a: Some(1) }), "X { a: Some(1)! }");
+}
+
+#[test]
+#[allow(dead_code)]
+fn redact() {
+    #[derive_ex(Debug)]
+    struct X {
+        user: String,
+        #[debug(redact)]
+        password: String,
+    }
+    let a = X {
+        user: "alice".into(),
+        password: "secret".into(),
+    };
+    assert_eq!(format!("{a:?}"), r#"X { user: "alice", password: *** }"#);
+}
+
+#[test]
+#[allow(dead_code)]
+fn redact_type_with_field_not_redacted() {
+    #[derive_ex(Debug)]
+    #[debug(redact)]
+    struct X {
+        #[debug(redact = false, format = "<{}>")]
+        user: String,
+        password: String,
+    }
+    let a = X {
+        user: "alice".into(),
+        password: "secret".into(),
+    };
+    assert_eq!(format!("{a:?}"), "X { user: <alice>, password: *** }");
+}
+
+#[test]
+#[allow(dead_code)]
+fn redact_all() {
+    #[derive_ex(Debug)]
+    #[debug(redact)]
+    struct X {
+        #[debug(redact = false)]
+        user: String,
+        password: String,
+        token: String,
+    }
+    let a = X {
+        user: "alice".into(),
+        password: "secret".into(),
+        token: "token".into(),
+    };
+    assert_eq!(
+        format!("{a:?}"),
+        r#"X { user: "alice", password: ***, token: *** }"#
+    );
+}
+
+#[test]
+#[allow(dead_code)]
+fn redact_transparent() {
+    #[derive_ex(Debug)]
+    struct X(#[debug(transparent, redact)] String);
+    assert_eq!(format!("{:?}", X("secret".into())), "***");
+}
+
+#[test]
+#[allow(dead_code)]
+fn generics_redact() {
+    use std::fmt::Debug;
+
+    #[derive_ex(Debug)]
+    struct X<T>(#[debug(redact)] T);
+
+    struct NoDebug;
+
+    assert_impl!(Debug, X<NoDebug>);
+}
+
+#[test]
+#[allow(dead_code)]
+fn flatten() {
+    #[derive_ex(Debug)]
+    #[debug(flattenable)]
+    struct Inner {
+        b: u32,
+        #[debug(rename = "C")]
+        c: u32,
+        #[debug(skip)]
+        d: u32,
+    }
+
+    #[derive_ex(Debug)]
+    struct X {
+        a: u32,
+        #[debug(flatten)]
+        inner: Inner,
+        e: u32,
+    }
+    let a = X {
+        a: 1,
+        inner: Inner { b: 2, c: 3, d: 4 },
+        e: 5,
+    };
+    assert_eq!(format!("{a:?}"), "X { a: 1, b: 2, C: 3, e: 5, .. }");
+    assert_eq!(format!("{:?}", a.inner), "Inner { b: 2, C: 3, .. }");
+}
+
+#[test]
+#[allow(dead_code)]
+fn flatten_non_exhaustive_false() {
+    #[derive_ex(Debug)]
+    #[debug(flattenable)]
+    #[non_exhaustive]
+    struct Inner {
+        b: u32,
+    }
+
+    #[derive_ex(Debug)]
+    #[debug(non_exhaustive = false)]
+    struct X {
+        a: u32,
+        #[debug(flatten)]
+        inner: Inner,
+    }
+    let a = X {
+        a: 1,
+        inner: Inner { b: 2 },
+    };
+    assert_eq!(format!("{a:?}"), "X { a: 1, b: 2 }");
+}
+
+#[test]
+#[allow(dead_code)]
+fn flatten_redact_false() {
+    #[derive_ex(Debug)]
+    #[debug(flattenable)]
+    struct Inner {
+        b: u32,
+    }
+
+    #[derive_ex(Debug)]
+    #[debug(redact)]
+    struct X {
+        a: u32,
+        #[debug(flatten, redact = false)]
+        inner: Inner,
+    }
+    let a = X {
+        a: 1,
+        inner: Inner { b: 2 },
+    };
+    assert_eq!(format!("{a:?}"), "X { a: ***, b: 2 }");
+}
+
+#[test]
+#[allow(dead_code)]
+fn flatten_path() {
+    mod m {
+        use derive_ex::derive_ex;
+
+        #[derive_ex(Debug)]
+        #[debug(flattenable)]
+        pub struct Inner {
+            pub b: u32,
+        }
+    }
+
+    #[derive_ex(Debug)]
+    struct X {
+        a: u32,
+        #[debug(flatten)]
+        inner: m::Inner,
+    }
+    let a = X {
+        a: 1,
+        inner: m::Inner { b: 2 },
+    };
+    assert_eq!(format!("{a:?}"), "X { a: 1, b: 2 }");
+}
+
+#[test]
+#[allow(dead_code)]
+fn flatten_nested() {
+    #[derive_ex(Debug)]
+    #[debug(flattenable)]
+    struct Inner2 {
+        c: u32,
+    }
+
+    #[derive_ex(Debug)]
+    #[debug(flattenable)]
+    struct Inner1 {
+        b: u32,
+        #[debug(flatten)]
+        inner: Inner2,
+    }
+
+    #[derive_ex(Debug)]
+    struct X {
+        a: u32,
+        #[debug(flatten)]
+        inner: Inner1,
+    }
+    let a = X {
+        a: 1,
+        inner: Inner1 {
+            b: 2,
+            inner: Inner2 { c: 3 },
+        },
+    };
+    assert_eq!(format!("{a:?}"), "X { a: 1, b: 2, c: 3 }");
+}
+
+#[test]
+#[allow(dead_code)]
+fn generics_flatten() {
+    use std::fmt::Debug;
+
+    #[derive_ex(Debug)]
+    #[debug(flattenable)]
+    struct Inner<T> {
+        a: T,
+    }
+
+    #[derive_ex(Debug)]
+    struct X<T> {
+        #[debug(flatten)]
+        inner: Inner<T>,
+    }
+
+    struct NoDebug;
+
+    assert_impl!(Debug, X<u32>);
+    assert_impl!(!Debug, X<NoDebug>);
+    assert_eq!(
+        format!(
+            "{:?}",
+            X {
+                inner: Inner { a: 1 }
+            }
+        ),
+        "X { a: 1 }"
+    );
+}
+
+#[test]
+#[allow(dead_code)]
+fn limit() {
+    #[derive_ex(Debug)]
+    struct X {
+        #[debug(limit = 3)]
+        a: Vec<u32>,
+    }
+    assert_eq!(
+        format!(
+            "{:?}",
+            X {
+                a: (0..100).collect()
+            }
+        ),
+        "X { a: [0, 1, 2, ... (97 more)] }"
+    );
+    assert_eq!(
+        format!("{:?}", X { a: vec![0, 1, 2] }),
+        "X { a: [0, 1, 2] }"
+    );
+    assert_eq!(format!("{:?}", X { a: vec![] }), "X { a: [] }");
+}
+
+#[test]
+#[allow(dead_code)]
+fn limit_inexact_size() {
+    struct Evens(Vec<u32>);
+    impl<'a> IntoIterator for &'a Evens {
+        type Item = &'a u32;
+        type IntoIter = std::iter::Filter<std::slice::Iter<'a, u32>, fn(&&u32) -> bool>;
+        fn into_iter(self) -> Self::IntoIter {
+            self.0.iter().filter(|x| **x % 2 == 0)
+        }
+    }
+
+    #[derive_ex(Debug)]
+    struct X {
+        #[debug(limit = 2)]
+        a: Evens,
+    }
+    assert_eq!(
+        format!(
+            "{:?}",
+            X {
+                a: Evens(vec![0, 1, 2, 3, 4])
+            }
+        ),
+        "X { a: [0, 2, ...] }"
+    );
+    assert_eq!(
+        format!(
+            "{:?}",
+            X {
+                a: Evens(vec![0, 1, 2, 3])
+            }
+        ),
+        "X { a: [0, 2] }"
+    );
+}
+
+#[test]
+#[allow(dead_code)]
+fn limit_infinite() {
+    struct Naturals;
+    impl IntoIterator for &Naturals {
+        type Item = u64;
+        type IntoIter = std::ops::RangeFrom<u64>;
+        fn into_iter(self) -> Self::IntoIter {
+            0..
+        }
+    }
+
+    #[derive_ex(Debug)]
+    struct X {
+        #[debug(limit = 3)]
+        a: Naturals,
+    }
+    assert_eq!(
+        format!("{:?}", X { a: Naturals }),
+        "X { a: [0, 1, 2, ...] }"
+    );
+}
+
+#[test]
+#[allow(dead_code)]
+fn limit_transparent() {
+    #[derive_ex(Debug)]
+    struct X(#[debug(transparent, limit = 2)] Vec<u32>);
+    assert_eq!(format!("{:?}", X(vec![1, 2, 3, 4])), "[1, 2, ... (2 more)]");
+}
+
+#[test]
+#[allow(dead_code)]
+fn generics_limit() {
+    use std::fmt::Debug;
+
+    #[derive_ex(Debug)]
+    struct X<T>(#[debug(limit = 2)] Vec<T>);
+
+    struct NoDebug;
+
+    assert_impl!(Debug, X<u32>);
+    assert_impl!(!Debug, X<NoDebug>);
+    assert_eq!(format!("{:?}", X(vec![1, 2, 3])), "X([1, 2, ... (1 more)])");
+}
+
+#[test]
+#[allow(dead_code)]
+fn display_field() {
+    #[derive_ex(Debug)]
+    struct X {
+        #[debug(display)]
+        a: String,
+        b: String,
+    }
+    let a = X {
+        a: "x\ty".into(),
+        b: "x\ty".into(),
+    };
+    assert_eq!(format!("{a:?}"), r#"X { a: x	y, b: "x\ty" }"#);
+}
+
+#[test]
+#[allow(dead_code)]
+fn generics_display_field() {
+    use std::fmt::Debug;
+
+    #[derive_ex(Debug)]
+    struct X<T>(#[debug(display)] T);
+
+    #[derive(Debug)]
+    struct NoDisplay;
+
+    assert_impl!(Debug, X<u32>);
+    assert_impl!(!Debug, X<NoDisplay>);
+}
+
+#[test]
+fn display() {
+    use std::fmt::{Display, Formatter, Result};
+
+    #[derive_ex(Debug(display))]
+    struct X(String);
+
+    impl Display for X {
+        fn fmt(&self, f: &mut Formatter) -> Result {
+            write!(f, "{}", self.0)
+        }
+    }
+    assert_eq!(format!("{:?}", X("a\"b".into())), "a\"b");
+}
+
+#[test]
+fn display_bound() {
+    use std::fmt::{Debug, Display, Formatter, Result};
+
+    #[derive_ex(Debug(display))]
+    #[debug(bound(T : Display))]
+    struct X<T>(T);
+
+    impl<T: Display> Display for X<T> {
+        fn fmt(&self, f: &mut Formatter) -> Result {
+            self.0.fmt(f)
+        }
+    }
+
+    struct NoDisplay;
+
+    assert_impl!(Debug, X<u32>);
+    assert_impl!(!Debug, X<NoDisplay>);
+    assert_eq!(format!("{:?}", X(1)), "1");
+}
+
+#[test]
+fn generics_display() {
+    use std::fmt::{Debug, Display, Formatter, Result};
+
+    #[derive_ex(Debug(display))]
+    struct X<T>(T);
+
+    impl<T: Display> Display for X<T> {
+        fn fmt(&self, f: &mut Formatter) -> Result {
+            self.0.fmt(f)
+        }
+    }
+
+    #[derive(Debug)]
+    struct NoDisplay;
+
+    assert_impl!(Debug, X<u32>);
+    assert_impl!(!Debug, X<NoDisplay>);
+}
@@ -0,0 +1,86 @@
+use derive_ex::derive_ex;
+use derive_ex_tests::assert_impl;
+
+#[test]
+#[allow(dead_code)]
+fn opaque() {
+    #[derive_ex(Debug)]
+    union X {
+        a: u32,
+        b: f32,
+    }
+    assert_eq!(format!("{:?}", X { a: 1 }), "X { .. }");
+}
+
+#[test]
+#[allow(dead_code)]
+fn rename() {
+    #[derive_ex(Debug)]
+    #[debug(rename = "Y")]
+    union X {
+        a: u32,
+    }
+    assert_eq!(format!("{:?}", X { a: 1 }), "Y { .. }");
+}
+
+#[test]
+#[allow(dead_code)]
+fn with() {
+    use std::fmt::{Formatter, Result};
+
+    fn fmt_x(x: &X, f: &mut Formatter) -> Result {
+        write!(f, "X({:#x})", unsafe { x.a })
+    }
+
+    #[derive_ex(Debug)]
+    #[debug(with = fmt_x)]
+    union X {
+        a: u32,
+        b: f32,
+    }
+    assert_eq!(format!("{:?}", X { a: 255 }), "X(0xff)");
+}
+
+#[test]
+#[allow(dead_code)]
+fn generics() {
+    use std::fmt::Debug;
+
+    #[derive_ex(Debug)]
+    union X<T: Copy> {
+        a: T,
+    }
+
+    #[derive(Clone, Copy)]
+    struct NoDebug;
+
+    assert_impl!(Debug, X<NoDebug>);
+}
+
+#[test]
+#[allow(dead_code)]
+fn derive_macro() {
+    #[derive(derive_ex::Ex)]
+    #[derive_ex(Debug)]
+    union X {
+        a: u32,
+    }
+    assert_eq!(format!("{:?}", X { a: 1 }), "X { .. }");
+}
+
+#[test]
+fn display() {
+    use std::fmt::{Display, Formatter, Result};
+
+    #[derive_ex(Debug(display))]
+    union X {
+        a: u32,
+    }
+
+    impl Display for X {
+        fn fmt(&self, f: &mut Formatter) -> Result {
+            write!(f, "{}", unsafe { self.a })
+        }
+    }
+    assert_eq!(format!("{:?}", X { a: 1 }), "1");
+}
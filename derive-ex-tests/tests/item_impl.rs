@@ -1,4 +1,5 @@
 use derive_ex::derive_ex;
+use derive_ex_tests::assert_impl;
 
 #[test]
 fn add() {
@@ -491,3 +492,698 @@ fn bitxor() {
     assert_eq!((&X(0b101) ^ X(0b011)).0, 0b110);
     assert_eq!((&X(0b101) ^ &X(0b011)).0, 0b110);
 }
+
+#[test]
+fn neg() {
+    use std::ops::Neg;
+
+    #[derive(Clone)]
+    struct X(i32);
+
+    #[derive_ex(Neg)]
+    impl Neg for X {
+        type Output = Self;
+        fn neg(self) -> Self::Output {
+            X(-self.0)
+        }
+    }
+
+    assert_eq!((-X(10)).0, -10);
+    assert_eq!((-&X(10)).0, -10);
+}
+
+#[test]
+fn neg_by_ref() {
+    use std::ops::Neg;
+
+    struct X(i32);
+
+    #[derive_ex(Neg)]
+    impl Neg for &X {
+        type Output = X;
+        fn neg(self) -> Self::Output {
+            X(-self.0)
+        }
+    }
+
+    assert_eq!((-X(10)).0, -10);
+    assert_eq!((-&X(10)).0, -10);
+}
+
+#[test]
+fn neg_generics() {
+    use std::ops::Neg;
+
+    #[derive(Clone)]
+    struct X<T>(T);
+
+    #[derive_ex(Neg)]
+    impl<T> Neg for X<T>
+    where
+        T: Neg<Output = T> + Clone,
+    {
+        type Output = Self;
+        fn neg(self) -> Self::Output {
+            X(-self.0)
+        }
+    }
+
+    assert_eq!((-X(10)).0, -10);
+    assert_eq!((-&X(10)).0, -10);
+}
+
+#[test]
+fn not() {
+    use std::ops::Not;
+
+    #[derive(Clone)]
+    struct X(bool);
+
+    #[derive_ex(Not)]
+    impl Not for X {
+        type Output = bool;
+        fn not(self) -> Self::Output {
+            !self.0
+        }
+    }
+
+    assert!(!X(false));
+    assert!(!&X(false));
+}
+
+#[test]
+fn add_assign_by_add_assign_rhs_value() {
+    use std::ops::AddAssign;
+
+    #[derive(Clone)]
+    struct X(u32);
+
+    #[derive_ex(AddAssign)]
+    impl AddAssign for X {
+        fn add_assign(&mut self, rhs: Self) {
+            self.0 += rhs.0;
+        }
+    }
+
+    let mut x = X(10);
+    x += X(2);
+    x += &X(3);
+    assert_eq!(x.0, 15);
+}
+
+#[test]
+fn add_assign_by_add_assign_rhs_ref() {
+    use std::ops::AddAssign;
+
+    struct X(u32);
+
+    #[derive_ex(AddAssign)]
+    impl AddAssign<&X> for X {
+        fn add_assign(&mut self, rhs: &X) {
+            self.0 += rhs.0;
+        }
+    }
+
+    let mut x = X(10);
+    x += X(2);
+    x += &X(3);
+    assert_eq!(x.0, 15);
+}
+
+#[test]
+fn add_assign_by_add_assign_generics() {
+    use std::ops::AddAssign;
+
+    #[derive(Clone)]
+    struct X<T>(T);
+
+    #[derive_ex(AddAssign)]
+    impl<T> AddAssign<&X<T>> for X<T>
+    where
+        T: AddAssign + Clone,
+    {
+        fn add_assign(&mut self, rhs: &X<T>) {
+            self.0 += rhs.0.clone();
+        }
+    }
+
+    let mut x = X(10);
+    x += X(2);
+    x += &X(3);
+    assert_eq!(x.0, 15);
+}
+
+#[test]
+fn add_add_assign_by_add_assign() {
+    use std::ops::AddAssign;
+
+    #[derive(Clone)]
+    struct X(u32);
+
+    #[derive_ex(Add, AddAssign)]
+    impl AddAssign<&X> for X {
+        fn add_assign(&mut self, rhs: &X) {
+            self.0 += rhs.0;
+        }
+    }
+
+    let mut x = X(10);
+    x += X(2);
+    assert_eq!(x.0, 12);
+    assert_eq!((X(10) + &X(2)).0, 12);
+}
+
+#[test]
+fn sub_by_add() {
+    use std::ops::{Add, Neg};
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct X(i32);
+
+    impl Neg for X {
+        type Output = Self;
+        fn neg(self) -> Self::Output {
+            X(-self.0)
+        }
+    }
+
+    #[derive_ex(Add, Sub)]
+    impl Add for X {
+        type Output = Self;
+        fn add(self, rhs: Self) -> Self::Output {
+            X(self.0 + rhs.0)
+        }
+    }
+
+    assert_eq!(X(10) - X(2), X(8));
+    assert_eq!(&X(10) + X(2), X(12));
+}
+
+#[test]
+fn sub_by_add_generics() {
+    use std::ops::{Add, Neg};
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct X<T>(T);
+
+    impl<T: Neg<Output = T>> Neg for X<T> {
+        type Output = Self;
+        fn neg(self) -> Self::Output {
+            X(-self.0)
+        }
+    }
+
+    #[derive_ex(Sub)]
+    impl<T: Add<Output = T>> Add for X<T> {
+        type Output = Self;
+        fn add(self, rhs: Self) -> Self::Output {
+            X(self.0 + rhs.0)
+        }
+    }
+
+    assert_eq!(X(10) - X(2), X(8));
+}
+
+#[test]
+fn div_by_mul() {
+    use std::ops::Mul;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct X(f64);
+
+    impl X {
+        fn recip(self) -> Self {
+            X(1.0 / self.0)
+        }
+    }
+
+    #[derive_ex(Mul, Div(reciprocal = X::recip))]
+    impl Mul for X {
+        type Output = Self;
+        fn mul(self, rhs: Self) -> Self::Output {
+            X(self.0 * rhs.0)
+        }
+    }
+
+    assert_eq!(X(10.0) / X(2.0), X(5.0));
+    assert_eq!(&X(10.0) * X(2.0), X(20.0));
+}
+
+#[test]
+fn add_by_ref_ref_lifetime() {
+    use std::ops::Add;
+
+    struct X(u32);
+
+    #[derive_ex(Add)]
+    impl<'a> Add<&'a X> for &'a X {
+        type Output = X;
+        fn add(self, rhs: &'a X) -> Self::Output {
+            X(self.0 + rhs.0)
+        }
+    }
+
+    assert_eq!((X(10) + X(2)).0, 12);
+    assert_eq!((&X(10) + X(2)).0, 12);
+    assert_eq!((X(10) + &X(2)).0, 12);
+    assert_eq!((&X(10) + &X(2)).0, 12);
+}
+
+#[test]
+#[allow(clippy::needless_lifetimes)]
+fn add_by_ref_ref_distinct_lifetimes() {
+    use std::ops::Add;
+
+    struct X(u32);
+
+    #[derive_ex(Add, AddAssign)]
+    impl<'a, 'b> Add<&'b X> for &'a X {
+        type Output = X;
+        fn add(self, rhs: &'b X) -> Self::Output {
+            X(self.0 + rhs.0)
+        }
+    }
+
+    assert_eq!((X(10) + X(2)).0, 12);
+    assert_eq!((&X(10) + X(2)).0, 12);
+    assert_eq!((X(10) + &X(2)).0, 12);
+    assert_eq!((&X(10) + &X(2)).0, 12);
+
+    let mut x = X(10);
+    x += &X(2);
+    assert_eq!(x.0, 12);
+}
+
+#[test]
+#[allow(clippy::needless_lifetimes)]
+#[deny(unused_lifetimes)]
+fn add_by_ref_ref_bounded_lifetimes() {
+    use std::ops::Add;
+
+    struct X(u32);
+
+    #[derive_ex(Add)]
+    impl<'a, 'b: 'a> Add<&'b X> for &'a X {
+        type Output = X;
+        fn add(self, rhs: &'b X) -> Self::Output {
+            X(self.0 + rhs.0)
+        }
+    }
+
+    assert_eq!((X(10) + X(2)).0, 12);
+    assert_eq!((&X(10) + X(2)).0, 12);
+    assert_eq!((X(10) + &X(2)).0, 12);
+    assert_eq!((&X(10) + &X(2)).0, 12);
+}
+
+#[test]
+fn add_by_ref_value_lifetime_generics() {
+    use std::ops::Add;
+
+    #[derive(Clone)]
+    struct X<'a, T>(&'a [T], u32);
+
+    #[derive_ex(Add)]
+    impl<'a, 'b, T: Clone> Add<&'b X<'a, T>> for X<'a, T> {
+        type Output = Self;
+        fn add(self, rhs: &'b X<'a, T>) -> Self::Output {
+            X(self.0, self.1 + rhs.1)
+        }
+    }
+
+    let s = [1, 2];
+    assert_eq!((X(&s, 10) + X(&s, 2)).1, 12);
+    assert_eq!((&X(&s, 10) + X(&s, 2)).1, 12);
+    assert_eq!((X(&s, 10) + &X(&s, 2)).1, 12);
+    assert_eq!((&X(&s, 10) + &X(&s, 2)).1, 12);
+}
+
+#[test]
+fn add_assign_by_add_assign_lifetime() {
+    use std::ops::AddAssign;
+
+    struct X(u32);
+
+    #[derive_ex(Add, AddAssign)]
+    impl<'a> AddAssign<&'a X> for X {
+        fn add_assign(&mut self, rhs: &'a X) {
+            self.0 += rhs.0;
+        }
+    }
+
+    let mut x = X(10);
+    x += X(2);
+    x += &X(3);
+    assert_eq!(x.0, 15);
+    assert_eq!((X(10) + &X(2)).0, 12);
+}
+
+#[test]
+#[allow(clippy::needless_lifetimes)]
+fn neg_by_ref_lifetime() {
+    use std::ops::Neg;
+
+    struct X(i32);
+
+    #[derive_ex(Neg)]
+    impl<'a> Neg for &'a X {
+        type Output = X;
+        fn neg(self) -> Self::Output {
+            X(-self.0)
+        }
+    }
+
+    assert_eq!((-X(10)).0, -10);
+    assert_eq!((-&X(10)).0, -10);
+}
+
+#[test]
+#[allow(clippy::derive_ord_xor_partial_ord)]
+fn partial_ord_partial_eq_eq_by_ord() {
+    use std::cmp::Ordering;
+
+    struct X(u32, &'static str);
+
+    #[derive_ex(PartialOrd, PartialEq, Eq)]
+    impl Ord for X {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    assert!(X(1, "a") == X(1, "b"));
+    assert!(X(1, "a") != X(2, "a"));
+    assert!(X(1, "a") < X(2, "a"));
+    assert_eq!(X(2, "a").partial_cmp(&X(1, "a")), Some(Ordering::Greater));
+    assert_eq!(X(2, "a").max(X(1, "b")).1, "a");
+}
+
+#[test]
+#[allow(clippy::derive_ord_xor_partial_ord)]
+fn partial_ord_partial_eq_eq_by_ord_generics() {
+    use std::cmp::Ordering;
+
+    struct X<T>(T);
+
+    #[derive_ex(PartialOrd, PartialEq, Eq)]
+    impl<T: Ord> Ord for X<T> {
+        fn cmp(&self, other: &Self) -> Ordering {
+            other.0.cmp(&self.0)
+        }
+    }
+
+    assert!(X(1) == X(1));
+    assert!(X(1) > X(2));
+}
+
+#[test]
+#[allow(clippy::op_ref)]
+fn partial_eq_str() {
+    struct Name(String);
+
+    #[derive_ex(PartialEq)]
+    impl PartialEq<str> for Name {
+        fn eq(&self, other: &str) -> bool {
+            self.0 == other
+        }
+    }
+
+    let name = Name("abc".to_string());
+    assert!(name == *"abc");
+    assert!(*"abc" == name);
+    assert!(name == "abc");
+    assert!("abc" == name);
+    assert!(&name == "abc");
+    assert!(*"abc" == &name);
+    assert!(name != "abd");
+    assert!("abd" != name);
+    assert_impl!(!PartialEq<String>, Name);
+    assert_impl!(!PartialEq<Name>, String);
+}
+
+#[test]
+#[allow(clippy::cmp_owned)]
+fn partial_eq_str_string() {
+    struct Name(String);
+
+    #[derive_ex(PartialEq(string))]
+    impl PartialEq<str> for Name {
+        fn eq(&self, other: &str) -> bool {
+            self.0 == other
+        }
+    }
+
+    let name = Name("abc".to_string());
+    assert!(name == "abc");
+    assert!("abc" == name);
+    assert!(name == "abc".to_string());
+    assert!("abc".to_string() == name);
+}
+
+#[test]
+#[allow(clippy::op_ref)]
+fn partial_eq_same_type() {
+    struct X(u32);
+
+    #[derive_ex(PartialEq)]
+    impl PartialEq for X {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    assert!(X(1) == X(1));
+    assert!(X(1) == &X(1));
+    assert!(&X(1) == X(1));
+    assert!(&X(1) == &X(1));
+}
+
+#[test]
+#[allow(clippy::op_ref)]
+fn partial_eq_generics() {
+    struct X<T>(T);
+
+    #[derive_ex(PartialEq)]
+    impl<T: PartialEq> PartialEq<T> for X<T> {
+        fn eq(&self, other: &T) -> bool {
+            &self.0 == other
+        }
+    }
+
+    assert!(X(1) == 1);
+    assert!(X(1) == &1);
+}
+
+#[test]
+#[allow(clippy::op_ref)]
+fn partial_ord_u32() {
+    use std::cmp::Ordering;
+
+    struct X(u32);
+
+    #[derive_ex(PartialEq)]
+    impl PartialEq<u32> for X {
+        fn eq(&self, other: &u32) -> bool {
+            self.0 == *other
+        }
+    }
+
+    #[derive_ex(PartialOrd)]
+    impl PartialOrd<u32> for X {
+        fn partial_cmp(&self, other: &u32) -> Option<Ordering> {
+            self.0.partial_cmp(other)
+        }
+    }
+
+    assert!(X(1) < 2);
+    assert!(2 > X(1));
+    assert!(X(1) < &2);
+    assert!(&2 > X(1));
+    assert!(&X(1) < 2);
+    assert!(2 > &X(1));
+    assert_eq!(2.partial_cmp(&X(1)), Some(Ordering::Greater));
+}
+
+#[test]
+fn default_by_new() {
+    #[derive(Debug, PartialEq)]
+    struct X(u32);
+
+    #[derive_ex(Default)]
+    impl X {
+        fn new() -> Self {
+            X(10)
+        }
+    }
+
+    assert_eq!(X::default(), X(10));
+}
+
+#[test]
+fn default_by_new_generics() {
+    #[derive(Debug, PartialEq)]
+    struct X<T>(Vec<T>);
+
+    #[derive_ex(Default)]
+    impl<T> X<T> {
+        fn new() -> Self {
+            X(Vec::new())
+        }
+    }
+
+    assert_eq!(X::<u8>::default(), X(Vec::new()));
+}
+
+#[test]
+fn from_by_new() {
+    #[derive(Debug, PartialEq)]
+    struct X(u32);
+
+    #[derive_ex(From)]
+    impl X {
+        fn new(value: u16) -> Self {
+            X(value as u32 * 2)
+        }
+    }
+
+    assert_eq!(X::from(5u16), X(10));
+    let x: X = 5u16.into();
+    assert_eq!(x, X(10));
+}
+
+#[test]
+fn from_str_by_parse() {
+    use std::num::ParseIntError;
+
+    #[derive(Debug, PartialEq)]
+    struct X(u32);
+
+    #[derive_ex(FromStr)]
+    impl X {
+        fn parse(s: &str) -> Result<Self, ParseIntError> {
+            Ok(X(s.parse()?))
+        }
+    }
+
+    assert_eq!("10".parse::<X>(), Ok(X(10)));
+    assert!("a".parse::<X>().is_err());
+}
+
+#[test]
+fn from_str_and_default() {
+    #[derive(Debug, PartialEq)]
+    struct X(String);
+
+    #[derive_ex(Default, FromStr)]
+    impl X {
+        fn new() -> Self {
+            X("default".into())
+        }
+        fn parse(s: &str) -> Result<X, std::convert::Infallible> {
+            Ok(X(s.into()))
+        }
+    }
+
+    assert_eq!(X::default(), X("default".into()));
+    assert_eq!("a".parse::<X>(), Ok(X("a".into())));
+}
+
+#[test]
+fn as_ref_borrow_by_deref() {
+    use std::borrow::Borrow;
+    use std::collections::HashSet;
+    use std::ops::Deref;
+
+    #[derive(Hash, PartialEq, Eq)]
+    struct X(String);
+
+    #[derive_ex(AsRef, Borrow)]
+    impl Deref for X {
+        type Target = str;
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+
+    let x = X("abc".into());
+    let s: &str = x.as_ref();
+    assert_eq!(s, "abc");
+    let s: &str = x.borrow();
+    assert_eq!(s, "abc");
+
+    let mut set = HashSet::new();
+    set.insert(X("abc".into()));
+    assert!(set.contains("abc"));
+}
+
+#[test]
+fn as_ref_by_deref_generics() {
+    use std::ops::Deref;
+
+    struct X<T>(Box<T>);
+
+    #[derive_ex(AsRef)]
+    impl<T> Deref for X<T> {
+        type Target = T;
+        fn deref(&self) -> &T {
+            &self.0
+        }
+    }
+
+    let x = X(Box::new(10));
+    let v: &i32 = x.as_ref();
+    assert_eq!(*v, 10);
+}
+
+#[test]
+fn debug_by_display() {
+    use std::fmt::{Display, Formatter, Result};
+
+    struct Version(u32, u32);
+
+    #[derive_ex(Debug)]
+    impl Display for Version {
+        fn fmt(&self, f: &mut Formatter) -> Result {
+            write!(f, "{}.{}", self.0, self.1)
+        }
+    }
+
+    assert_eq!(format!("{:?}", Version(1, 2)), "1.2");
+}
+
+#[test]
+fn debug_by_display_quoted() {
+    use std::fmt::{Display, Formatter, Result};
+
+    struct Ident(&'static str);
+
+    #[derive_ex(Debug(quoted))]
+    impl Display for Ident {
+        fn fmt(&self, f: &mut Formatter) -> Result {
+            write!(f, "{}", self.0)
+        }
+    }
+
+    assert_eq!(format!("{:?}", Ident("a\"b")), r#""a\"b""#);
+    for s in ["a'b", "a\nb\\c", "\u{301}a\u{301}", "\u{7f}\0", "日本"] {
+        assert_eq!(format!("{:?}", Ident(s)), format!("{s:?}"));
+    }
+}
+
+#[test]
+fn debug_by_display_type_name() {
+    use std::fmt::{Display, Formatter, Result};
+
+    struct Ident<T>(T);
+
+    #[derive_ex(Debug(type_name))]
+    impl<T: Display> Display for Ident<T> {
+        fn fmt(&self, f: &mut Formatter) -> Result {
+            write!(f, "{}", self.0)
+        }
+    }
+
+    assert_eq!(format!("{:?}", Ident("abc")), r#"Ident("abc")"#);
+    assert_eq!(format!("{:?}", Ident("a'\"b")), r#"Ident("a'\"b")"#);
+    assert_eq!(format!("{:#?}", Ident("abc")), "Ident(\n    \"abc\",\n)");
+}
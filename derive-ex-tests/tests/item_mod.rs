@@ -0,0 +1,112 @@
+use derive_ex::derive_ex;
+use derive_ex_tests::assert_impl;
+
+#[test]
+fn mod_derive() {
+    #[derive_ex(Clone, Debug)]
+    mod m {
+        pub struct A(pub u32);
+        pub enum B {
+            X,
+            Y(u32),
+        }
+    }
+
+    assert_impl!(Clone, m::A, m::B);
+    assert_eq!(format!("{:?}", m::A(1).clone()), "A(1)");
+    assert_eq!(format!("{:?}", m::B::Y(2).clone()), "Y(2)");
+    assert_eq!(format!("{:?}", m::B::X), "X");
+    assert_eq!(m::A(1).clone().0, 1);
+    assert!(matches!(m::B::Y(2).clone(), m::B::Y(2)));
+}
+
+#[test]
+#[allow(dead_code)]
+fn mod_derive_union() {
+    use std::fmt::Debug;
+
+    #[derive_ex(Clone, Copy, Debug)]
+    mod m {
+        pub union U {
+            pub a: u32,
+        }
+
+        #[derive(Clone, Copy)]
+        pub union V {
+            pub a: u32,
+        }
+    }
+
+    assert_impl!(Debug, m::U, m::V);
+    assert_impl!(!Clone, m::U);
+    assert_impl!(Clone, m::V);
+    assert_eq!(format!("{:?}", m::U { a: 1 }), "U { .. }");
+}
+
+#[test]
+fn mod_derive_with_item_derive() {
+    #[derive_ex(Clone, Debug)]
+    mod m {
+        #[derive(PartialEq)]
+        #[derive_ex(Default)]
+        pub struct A(pub u32);
+    }
+
+    assert_eq!(m::A::default(), m::A(0));
+    assert_eq!(format!("{:?}", m::A::default().clone()), "A(0)");
+}
+
+#[test]
+fn mod_derive_override() {
+    use std::marker::PhantomData;
+
+    #[derive_ex(Clone)]
+    mod m {
+        use std::marker::PhantomData;
+
+        pub struct A<T>(pub PhantomData<T>);
+
+        #[derive_ex(Clone(bound(T)))]
+        pub struct B<T>(pub PhantomData<T>);
+    }
+
+    struct NotClone;
+    assert_impl!(Clone, m::A<NotClone>);
+    assert_impl!(!Clone, m::B<NotClone>);
+    let _ = m::B(PhantomData::<u8>).clone();
+}
+
+#[test]
+fn mod_derive_bound() {
+    use std::marker::PhantomData;
+
+    #[derive_ex(Clone, bound(T))]
+    mod m {
+        use std::marker::PhantomData;
+
+        pub struct A<T>(pub PhantomData<T>);
+    }
+
+    struct NotClone;
+    assert_impl!(Clone, m::A<u8>);
+    assert_impl!(!Clone, m::A<NotClone>);
+    let _ = m::A(PhantomData::<u8>).clone();
+}
+
+#[test]
+fn mod_derive_skip() {
+    #[derive_ex(Clone)]
+    mod m {
+        pub struct A;
+
+        #[derive_ex(skip)]
+        pub struct B;
+
+        #[derive_ex(Default, skip)]
+        pub struct C;
+    }
+
+    assert_impl!(Clone, m::A);
+    assert_impl!(!Clone, m::B, m::C);
+    assert_impl!(Default, m::C);
+}
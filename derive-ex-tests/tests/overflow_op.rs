@@ -0,0 +1,99 @@
+use derive_ex::derive_ex;
+
+#[test]
+fn checked_add() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(CheckedAdd)]
+    struct X {
+        a: u8,
+        b: u32,
+    }
+
+    assert_eq!(
+        X { a: 10, b: 20 }.checked_add(X { a: 1, b: 2 }),
+        Some(X { a: 11, b: 22 })
+    );
+    assert_eq!(X { a: 255, b: 20 }.checked_add(X { a: 1, b: 2 }), None);
+    assert_eq!(X { a: 10, b: u32::MAX }.checked_add(X { a: 1, b: 2 }), None);
+}
+
+#[test]
+fn checked_unit_struct() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(CheckedAdd, WrappingAdd, SaturatingAdd)]
+    struct X;
+
+    assert_eq!(X.checked_add(X), Some(X));
+    assert_eq!(X.wrapping_add(X), X);
+    assert_eq!(X.saturating_add(X), X);
+}
+
+#[test]
+fn checked_div() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(CheckedDiv, CheckedRem)]
+    struct X(u8, i32);
+
+    assert_eq!(X(10, 20).checked_div(X(2, 5)), Some(X(5, 4)));
+    assert_eq!(X(10, 20).checked_div(X(0, 5)), None);
+    assert_eq!(X(10, 20).checked_rem(X(3, 6)), Some(X(1, 2)));
+    assert_eq!(X(10, i32::MIN).checked_div(X(3, -1)), None);
+}
+
+#[test]
+fn wrapping() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(WrappingAdd, WrappingSub, WrappingMul)]
+    struct X(u8, i8);
+
+    assert_eq!(X(200, 100).wrapping_add(X(100, 100)), X(44, -56));
+    assert_eq!(X(0, -128).wrapping_sub(X(1, 1)), X(255, 127));
+    assert_eq!(X(16, 64).wrapping_mul(X(16, 2)), X(0, -128));
+}
+
+#[test]
+fn saturating() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(SaturatingAdd, SaturatingSub, SaturatingMul)]
+    struct X(u8, i8);
+
+    assert_eq!(X(200, 100).saturating_add(X(100, 100)), X(255, 127));
+    assert_eq!(X(0, -128).saturating_sub(X(1, 1)), X(0, -128));
+    assert_eq!(X(16, 64).saturating_mul(X(16, 2)), X(255, 127));
+}
+
+#[test]
+fn nested() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(CheckedAdd)]
+    struct Inner(u8);
+
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(CheckedAdd)]
+    struct X(Inner, u8);
+
+    assert_eq!(
+        X(Inner(1), 2).checked_add(X(Inner(10), 20)),
+        Some(X(Inner(11), 22))
+    );
+    assert_eq!(X(Inner(255), 2).checked_add(X(Inner(10), 20)), None);
+}
+
+#[test]
+fn bound() {
+    trait MyCheckedAdd: Sized {
+        fn checked_add(self, rhs: Self) -> Option<Self>;
+    }
+    impl MyCheckedAdd for u8 {
+        fn checked_add(self, rhs: Self) -> Option<Self> {
+            u8::checked_add(self, rhs)
+        }
+    }
+
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(CheckedAdd(bound(T : MyCheckedAdd)))]
+    struct X<T>(T);
+
+    assert_eq!(X(1u8).checked_add(X(2u8)), Some(X(3u8)));
+    assert_eq!(X(255u8).checked_add(X(2u8)), None);
+}
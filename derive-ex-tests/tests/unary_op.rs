@@ -42,6 +42,48 @@ fn neg_generics() {
     assert_eq!(-&X(8, 20), X(-8, -20));
 }
 
+#[test]
+fn neg_by() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Neg)]
+    struct X {
+        #[neg(by = i8::wrapping_neg)]
+        a: i8,
+        b: i32,
+    }
+    assert_eq!(-X { a: -128, b: 20 }, X { a: -128, b: -20 });
+    assert_eq!(-&X { a: -128, b: 20 }, X { a: -128, b: -20 });
+}
+
+#[test]
+fn neg_array() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Neg)]
+    struct X([[i32; 2]; 2], (i8, [i16; 2]));
+
+    assert_eq!(
+        -X([[1, 2], [3, 4]], (5, [6, 7])),
+        X([[-1, -2], [-3, -4]], (-5, [-6, -7]))
+    );
+    assert_eq!(
+        -&X([[1, 2], [3, 4]], (5, [6, 7])),
+        X([[-1, -2], [-3, -4]], (-5, [-6, -7]))
+    );
+}
+
+#[test]
+fn neg_refs() {
+    use std::ops::Neg;
+
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Neg(refs = owned))]
+    struct X(i32);
+
+    assert_eq!(-X(1), X(-1));
+    assert_impl!(Neg, X);
+    assert_impl!(!Neg, &X);
+}
+
 #[test]
 fn neg_generics_contains_self() {
     trait MyTrait {}
@@ -146,3 +188,108 @@ fn not_record_struct() {
     assert_eq!(!X { a: true, b: false }, X { a: false, b: true });
     assert_eq!(!&X { a: true, b: false }, X { a: false, b: true });
 }
+
+#[test]
+fn neg_enum() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Neg)]
+    enum X {
+        A(i32, i8),
+        B { a: i32 },
+        C,
+    }
+
+    assert_eq!(-X::A(8, 20), X::A(-8, -20));
+    assert_eq!(-&X::A(8, 20), X::A(-8, -20));
+    assert_eq!(-X::B { a: 8 }, X::B { a: -8 });
+    assert_eq!(-&X::B { a: 8 }, X::B { a: -8 });
+    assert_eq!(-X::C, X::C);
+    assert_eq!(-&X::C, X::C);
+}
+
+#[test]
+fn neg_enum_empty() {
+    #[derive_ex(Neg)]
+    enum X {}
+}
+
+#[test]
+fn neg_enum_generics() {
+    use std::ops::Neg;
+
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Neg)]
+    enum X<T> {
+        A(T),
+        B,
+    }
+
+    assert_eq!(-X::A(8), X::A(-8));
+    assert_eq!(-&X::A(8), X::A(-8));
+    assert_eq!(-X::<i32>::B, X::B);
+    assert_impl!(!Neg, X<u32>, &X<u32>);
+}
+
+#[test]
+fn neg_enum_to() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Neg)]
+    enum Sign {
+        #[neg(to = Negative)]
+        Positive,
+        #[neg(to = Positive)]
+        Negative,
+        Zero,
+    }
+
+    assert_eq!(-Sign::Positive, Sign::Negative);
+    assert_eq!(-Sign::Negative, Sign::Positive);
+    assert_eq!(-&Sign::Positive, Sign::Negative);
+    assert_eq!(-Sign::Zero, Sign::Zero);
+}
+
+#[test]
+fn not_enum() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Not)]
+    enum X {
+        A(bool, [bool; 2]),
+        B,
+    }
+
+    assert_eq!(!X::A(true, [false, true]), X::A(false, [true, false]));
+    assert_eq!(!&X::A(true, [false, true]), X::A(false, [true, false]));
+    assert_eq!(!X::B, X::B);
+}
+
+#[test]
+fn not_enum_to() {
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Not)]
+    enum Switch {
+        #[not(to = Off)]
+        On,
+        #[not(to = On)]
+        Off,
+    }
+
+    assert_eq!(!Switch::On, Switch::Off);
+    assert_eq!(!Switch::Off, Switch::On);
+    assert_eq!(!&Switch::On, Switch::Off);
+    assert_eq!(!&Switch::Off, Switch::On);
+}
+
+#[test]
+fn not_enum_refs() {
+    use std::ops::Not;
+
+    #[derive(Eq, PartialEq, Debug)]
+    #[derive_ex(Not(refs = owned))]
+    enum X {
+        A(bool),
+    }
+
+    assert_eq!(!X::A(true), X::A(false));
+    assert_impl!(Not, X);
+    assert_impl!(!Not, &X);
+}
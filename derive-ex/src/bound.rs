@@ -3,7 +3,7 @@ use quote::quote;
 use structmeta::{NameArgs, ToTokens};
 use syn::{
     parse::{discouraged::Speculative, Parse, ParseStream},
-    Field, Generics, Result, Token, Type, WherePredicate,
+    parse_quote, Field, Generics, Path, Result, Token, Type, WherePredicate,
 };
 
 use crate::syn_utils::GenericParamSet;
@@ -101,11 +101,35 @@ impl WhereClauseBuilder {
         bounds.default
     }
     pub fn push_bounds_for_field(&mut self, field: &Field) {
-        if self.gps.contains_in_type(&field.ty) {
-            self.types.push(field.ty.clone());
+        self.push_bounds_for_type(&field.ty);
+    }
+    pub fn push_bounds_for_type(&mut self, ty: &Type) {
+        if self.gps.contains_in_type(ty) {
+            self.types.push(ty.clone());
+        }
+    }
+    pub fn push_preds_for_type(
+        &mut self,
+        ty: &Type,
+        preds: impl IntoIterator<Item = WherePredicate>,
+    ) {
+        if self.gps.contains_in_type(ty) {
+            self.preds.extend(preds);
+        }
+    }
+    pub fn push_bounds_for_field_with(&mut self, field: &Field, trait_: &Path) {
+        self.push_bounds_for_type_with(&field.ty, trait_);
+    }
+    pub fn push_bounds_for_type_with(&mut self, ty: &Type, trait_: &Path) {
+        if self.gps.contains_in_type(ty) {
+            self.preds.push(parse_quote!(#ty : #trait_));
         }
     }
 
+    pub fn types(&self) -> &[Type] {
+        &self.types
+    }
+
     pub fn build(self, f: impl Fn(&Type) -> TokenStream) -> TokenStream {
         let mut ws = Vec::new();
         for ty in &self.types {
@@ -56,9 +56,124 @@ impl BinaryOp {
             Self::Sub => "sub",
         }
     }
+    pub fn from_str_snake_case(s: &str) -> Option<Self> {
+        Some(match s {
+            "add" => Self::Add,
+            "bit_and" => Self::BitAnd,
+            "bit_or" => Self::BitOr,
+            "bit_xor" => Self::BitXor,
+            "div" => Self::Div,
+            "mul" => Self::Mul,
+            "rem" => Self::Rem,
+            "shl" => Self::Shl,
+            "shr" => Self::Shr,
+            "sub" => Self::Sub,
+            _ => return None,
+        })
+    }
+    pub fn to_str_snake_case(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::BitAnd => "bit_and",
+            Self::BitOr => "bit_or",
+            Self::BitXor => "bit_xor",
+            Self::Div => "div",
+            Self::Mul => "mul",
+            Self::Rem => "rem",
+            Self::Shl => "shl",
+            Self::Shr => "shr",
+            Self::Sub => "sub",
+        }
+    }
 }
 impl std::fmt::Display for BinaryOp {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.to_str())
     }
 }
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum UnaryOp {
+    Neg,
+    Not,
+}
+
+impl UnaryOp {
+    pub fn from_str(s: &str) -> Option<Self> {
+        Some(match s {
+            "Neg" => Self::Neg,
+            "Not" => Self::Not,
+            _ => return None,
+        })
+    }
+    pub fn to_str(self) -> &'static str {
+        match self {
+            UnaryOp::Neg => "Neg",
+            UnaryOp::Not => "Not",
+        }
+    }
+    pub fn to_func_name(self) -> &'static str {
+        match self {
+            UnaryOp::Neg => "neg",
+            UnaryOp::Not => "not",
+        }
+    }
+    pub fn from_str_snake_case(s: &str) -> Option<Self> {
+        Some(match s {
+            "neg" => Self::Neg,
+            "not" => Self::Not,
+            _ => return None,
+        })
+    }
+    pub fn to_str_snake_case(self) -> &'static str {
+        self.to_func_name()
+    }
+}
+impl std::fmt::Display for UnaryOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum OverflowMode {
+    Checked,
+    Wrapping,
+    Saturating,
+}
+
+impl OverflowMode {
+    pub const VARIANTS: &'static [Self] = &[Self::Checked, Self::Wrapping, Self::Saturating];
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Self::Checked => "Checked",
+            Self::Wrapping => "Wrapping",
+            Self::Saturating => "Saturating",
+        }
+    }
+    pub fn to_str_snake_case(self) -> &'static str {
+        match self {
+            Self::Checked => "checked",
+            Self::Wrapping => "wrapping",
+            Self::Saturating => "saturating",
+        }
+    }
+    pub fn is_supported(self, op: BinaryOp) -> bool {
+        match self {
+            Self::Checked | Self::Wrapping => matches!(
+                op,
+                BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem
+            ),
+            Self::Saturating => matches!(
+                op,
+                BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div
+            ),
+        }
+    }
+}
+impl std::fmt::Display for OverflowMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
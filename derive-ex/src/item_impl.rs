@@ -1,41 +1,127 @@
-use crate::{common::BinaryOp, syn_utils::expand_self};
+use crate::{
+    common::{BinaryOp, UnaryOp},
+    syn_utils::{expand_self, GenericParamSet},
+};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use std::fmt::Display;
-use structmeta::StructMeta;
+use std::{collections::HashSet, fmt::Display};
+use structmeta::{Flag, NameValue, Parse, StructMeta};
 use syn::{
-    parse2, parse_quote, spanned::Spanned, Error, GenericArgument, Ident, ImplItem, ItemImpl, Path,
-    PathArguments, PathSegment, Result, Type,
+    parse2, parse_quote, spanned::Spanned, token, visit::Visit, Error, Expr, FnArg,
+    GenericArgument, GenericParam, Generics, Ident, ImplItem, ItemImpl, Lifetime, Path,
+    PathArguments, PathSegment, Result, ReturnType, Signature, Type, WherePredicate,
 };
 
 #[derive(StructMeta, Debug)]
 #[struct_meta(name_filter = "snake_case")]
 struct ArgList {
     #[struct_meta(unnamed)]
-    items: Vec<Ident>,
+    items: Vec<ArgItem>,
     dump: bool,
 }
 
+#[derive(Parse, Debug)]
+struct ArgItem {
+    ident: Ident,
+    args: ArgItemArgsOption,
+}
+impl ArgItem {
+    fn args(&self) -> Option<&ArgItemArgs> {
+        match &self.args {
+            ArgItemArgsOption::Some { args, .. } => Some(args),
+            ArgItemArgsOption::None => None,
+        }
+    }
+    fn into_args(self) -> Option<ArgItemArgs> {
+        match self.args {
+            ArgItemArgsOption::Some { args, .. } => Some(args),
+            ArgItemArgsOption::None => None,
+        }
+    }
+}
+
+#[derive(StructMeta, Debug)]
+#[struct_meta(name_filter = "snake_case")]
+struct ArgItemArgs {
+    reciprocal: Option<NameValue<Expr>>,
+    quoted: Flag,
+    type_name: Flag,
+    string: Flag,
+}
+
+#[derive(Parse, Debug)]
+enum ArgItemArgsOption {
+    Some {
+        #[parse(peek)]
+        #[to_tokens("(")]
+        _paren: token::Paren,
+        args: ArgItemArgs,
+    },
+    None,
+}
+
 struct Args {
     dump: bool,
     make_binary: bool,
     make_assign: bool,
+    make_inverse: Option<Inverse>,
 }
 impl Args {
     fn from_attr_args(attr: TokenStream, op: Op) -> Result<Args> {
         let args: ArgList = parse2(attr)?;
         let mut make_binary = false;
         let mut make_assign = false;
-        for item in &args.items {
-            let target_op = Op::from_ident(item)?;
+        let mut make_inverse = None;
+        for item in args.items {
+            let target_op = Op::from_ident(&item.ident)?;
+            verify_debug_args(&item)?;
+            verify_string_arg(&item)?;
+            let span = item.ident.span();
+            let reciprocal = item.into_args().and_then(|args| args.reciprocal);
+            let inverse_op = inverse_op(op.op);
             if target_op.op != op.op {
+                if op.form == OpForm::Binary
+                    && target_op.form == OpForm::Binary
+                    && Some(target_op.op) == inverse_op
+                {
+                    make_inverse = Some(match target_op.op {
+                        BinaryOp::Div => {
+                            let Some(reciprocal) = reciprocal else {
+                                bail!(
+                                    span,
+                                    "`reciprocal = ...` is required to derive `{}` from `impl {}`",
+                                    target_op,
+                                    op
+                                );
+                            };
+                            Inverse::Reciprocal(reciprocal.value)
+                        }
+                        _ => Inverse::Neg,
+                    });
+                    continue;
+                }
+                if let (OpForm::Binary, Some(inverse_op)) = (op.form, inverse_op) {
+                    bail!(
+                        span,
+                        "expected `{}`, `{}` or `{}`",
+                        Op::new(op.op, OpForm::Binary),
+                        Op::new(op.op, OpForm::Assign),
+                        Op::new(inverse_op, OpForm::Binary)
+                    );
+                }
                 bail!(
-                    item.span(),
+                    span,
                     "expected `{}` or `{}`",
                     Op::new(op.op, OpForm::Binary),
                     Op::new(op.op, OpForm::Assign)
                 );
             }
+            if let Some(reciprocal) = reciprocal {
+                bail!(
+                    reciprocal.name_span,
+                    "`reciprocal = ...` can be used only to derive `Div` from `impl Mul`"
+                );
+            }
             match target_op.form {
                 OpForm::Binary => make_binary = true,
                 OpForm::Assign => make_assign = true,
@@ -45,10 +131,24 @@ impl Args {
             dump: args.dump,
             make_binary,
             make_assign,
+            make_inverse,
         })
     }
 }
 
+enum Inverse {
+    Neg,
+    Reciprocal(Expr),
+}
+
+fn inverse_op(op: BinaryOp) -> Option<BinaryOp> {
+    match op {
+        BinaryOp::Add => Some(BinaryOp::Sub),
+        BinaryOp::Mul => Some(BinaryOp::Div),
+        _ => None,
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 struct Op {
     op: BinaryOp,
@@ -111,11 +211,10 @@ enum OpForm {
 
 pub fn build_by_item_impl(attr: TokenStream, item_impl: &ItemImpl) -> Result<TokenStream> {
     let span = Span::call_site();
-    let message = "must be used with `impl {Trait} for {Type}`";
-    let t = item_impl
-        .trait_
-        .as_ref()
-        .ok_or_else(|| Error::new(span, message))?;
+    let message = "must be used with `impl {Trait} for {Type}` or `impl {Type}`";
+    let Some(t) = &item_impl.trait_ else {
+        return build_by_inherent_impl(attr, item_impl);
+    };
     if t.0.is_some() {
         bail!(span, "cannot use with negative trait");
     }
@@ -124,11 +223,25 @@ pub fn build_by_item_impl(attr: TokenStream, item_impl: &ItemImpl) -> Result<Tok
             .last()
             .ok_or_else(|| Error::new(span, message))?;
 
-    let this_orig = &item_impl.self_ty;
-    let (this, this_is_ref) = to_ref_elem(this_orig);
-    let rhs_orig = to_rhs(s, this_orig);
-    let (rhs, rhs_is_ref) = to_ref_elem(&rhs_orig);
-    let g = expand_self(&item_impl.generics, this_orig);
+    let trait_name = s.ident.to_string();
+    if let Some(op) = UnaryOp::from_str(&trait_name) {
+        return build_unary_op(attr, item_impl, op);
+    }
+    match trait_name.as_str() {
+        "Ord" => return build_by_ord(attr, item_impl),
+        "Deref" => return build_by_deref(attr, item_impl),
+        "Display" => return build_by_display(attr, item_impl),
+        "PartialEq" | "PartialOrd" => return build_by_partial_cmp(attr, item_impl, s),
+        _ => {}
+    }
+
+    let self_ty = &item_impl.self_ty;
+    let rhs_ty = to_rhs(s, self_ty);
+    let (this, this_is_ref) = to_ref_elem(self_ty);
+    let (rhs, rhs_is_ref) = to_ref_elem(&rhs_ty);
+    let this_orig = &ref_type_with(&this, this_is_ref);
+    let rhs_orig = ref_type_with(&rhs, rhs_is_ref);
+    let g = remove_ref_lifetimes(item_impl, &[self_ty, &rhs_ty])?;
     let (impl_g, _, where_g) = &g.split_for_impl();
 
     let op = Op::from_ident(&s.ident)?;
@@ -145,7 +258,7 @@ pub fn build_by_item_impl(attr: TokenStream, item_impl: &ItemImpl) -> Result<Tok
     let mut ts = TokenStream::new();
     match op.form {
         OpForm::Binary => {
-            let output = expand_self(find_output_type(item_impl)?, this_orig);
+            let output = expand_self(find_output_type(item_impl)?, self_ty);
             let impl_binary =
                 |impl_l_ref: bool, impl_r_ref: bool, call_l_ref: bool, call_r_ref: bool| {
                     if impl_l_ref == call_l_ref && impl_r_ref == call_r_ref {
@@ -196,15 +309,53 @@ pub fn build_by_item_impl(attr: TokenStream, item_impl: &ItemImpl) -> Result<Tok
                     ts.extend(impl_assign(&rhs_orig, this_is_ref));
                 }
             }
+            if let Some(inverse) = &args.make_inverse {
+                let inverse_op = Op::new(inverse_op(op.op).unwrap(), OpForm::Binary);
+                if rhs_is_ref {
+                    bail!(
+                        _,
+                        "`#[derive_ex({})]` cannot be used with `impl {}<&Rhs> for T`",
+                        inverse_op,
+                        binary_op
+                    );
+                }
+                let inverse_func = inverse_op.to_func_ident();
+                let inverse_trait = inverse_op.to_trait_path();
+                let mut g = g.clone();
+                let rhs_expr = match inverse {
+                    Inverse::Neg => {
+                        g.make_where_clause()
+                            .predicates
+                            .push(parse_quote!(#rhs_orig: ::core::ops::Neg<Output = #rhs_orig>));
+                        quote!(<#rhs_orig as ::core::ops::Neg>::neg(rhs))
+                    }
+                    Inverse::Reciprocal(f) => quote!((#f)(rhs)),
+                };
+                let (impl_g, _, where_g) = g.split_for_impl();
+                ts.extend(quote! {
+                    #[automatically_derived]
+                    impl #impl_g #inverse_trait<#rhs_orig> for #this_orig #where_g {
+                        type Output = #output;
+                        fn #inverse_func(self, rhs: #rhs_orig) -> Self::Output {
+                            <#this_orig as #binary_trait<#rhs_orig>>::#binary_func(self, #rhs_expr)
+                        }
+                    }
+                });
+            }
         }
         OpForm::Assign => {
             if args.make_assign {
-                bail!(
-                    _,
-                    "`#[derive_ex({})]` can be used only with `impl {} for T`",
-                    Op::new(op.op, OpForm::Assign),
-                    Op::new(op.op, OpForm::Binary),
-                );
+                let impl_rhs = ref_type_with(&rhs, !rhs_is_ref);
+                let r = ref_type_with(&rhs, rhs_is_ref);
+                let r_expr = change_owned(quote!(rhs), &rhs, !rhs_is_ref, rhs_is_ref);
+                ts.extend(quote! {
+                    #[automatically_derived]
+                    impl #impl_g #assign_trait<#impl_rhs> for #this_orig #where_g {
+                        fn #assign_func(&mut self, rhs: #impl_rhs) {
+                            <#this_orig as #assign_trait<#r>>::#assign_func(self, #r_expr)
+                        }
+                    }
+                });
             }
             if args.make_binary {
                 let this = this_orig;
@@ -228,24 +379,608 @@ pub fn build_by_item_impl(attr: TokenStream, item_impl: &ItemImpl) -> Result<Tok
     }
     Ok(ts)
 }
+fn build_unary_op(attr: TokenStream, item_impl: &ItemImpl, op: UnaryOp) -> Result<TokenStream> {
+    let args = parse_arg_list(attr, &[op.to_str()])?;
+    let self_ty = &item_impl.self_ty;
+    let (this, this_is_ref) = to_ref_elem(self_ty);
+    let g = remove_ref_lifetimes(item_impl, &[self_ty])?;
+    let (impl_g, _, where_g) = &g.split_for_impl();
+    let output = expand_self(find_output_type(item_impl)?, self_ty);
+    let func = Ident::new(op.to_func_name(), Span::call_site());
+    let trait_ident = Ident::new(op.to_str(), Span::call_site());
+    let trait_: Path = parse_quote!(::core::ops::#trait_ident);
+
+    let mut ts = TokenStream::new();
+    if !args.items.is_empty() {
+        let impl_this = ref_type_with(&this, !this_is_ref);
+        let l = ref_type_with(&this, this_is_ref);
+        let l_expr = change_owned(quote!(self), &this, !this_is_ref, this_is_ref);
+        ts.extend(quote! {
+            #[automatically_derived]
+            impl #impl_g #trait_ for #impl_this #where_g {
+                type Output = #output;
+                fn #func(self) -> Self::Output {
+                    <#l as #trait_>::#func(#l_expr)
+                }
+            }
+        });
+    }
+    if args.dump {
+        bail!(_, "{}", format!("dump:\n{ts}"));
+    }
+    Ok(ts)
+}
+fn build_by_ord(attr: TokenStream, item_impl: &ItemImpl) -> Result<TokenStream> {
+    let args = parse_arg_list(attr, &["PartialOrd", "PartialEq", "Eq"])?;
+    let this = &item_impl.self_ty;
+    let g = expand_self(&item_impl.generics, this);
+    let (impl_g, _, where_g) = &g.split_for_impl();
+
+    let mut ts = TokenStream::new();
+    for item in &args.items {
+        let body = match item.ident.to_string().as_str() {
+            "PartialOrd" => quote! {
+                fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                    ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+                }
+            },
+            "PartialEq" => quote! {
+                fn eq(&self, other: &Self) -> bool {
+                    ::core::cmp::Ord::cmp(self, other) == ::core::cmp::Ordering::Equal
+                }
+            },
+            _ => quote!(),
+        };
+        let trait_ident = &item.ident;
+        ts.extend(quote! {
+            #[automatically_derived]
+            impl #impl_g ::core::cmp::#trait_ident for #this #where_g {
+                #body
+            }
+        });
+    }
+    if args.dump {
+        bail!(_, "{}", format!("dump:\n{ts}"));
+    }
+    Ok(ts)
+}
+fn build_by_partial_cmp(
+    attr: TokenStream,
+    item_impl: &ItemImpl,
+    s: &PathSegment,
+) -> Result<TokenStream> {
+    let trait_ident = &s.ident;
+    let is_ord = trait_ident == "PartialOrd";
+    let args = parse_arg_list(attr, &[&trait_ident.to_string()])?;
+    let a: &Type = &item_impl.self_ty;
+    let b = &to_rhs(s, a);
+    let g = expand_self(&item_impl.generics, a);
+    let (impl_g, _, where_g) = &g.split_for_impl();
+    let trait_: Path = parse_quote!(::core::cmp::#trait_ident);
+    let string = args
+        .items
+        .iter()
+        .find_map(|item| item.args().and_then(|args| args.string.span));
+    if let Some(span) = string {
+        if b != &parse_quote!(str) {
+            bail!(
+                span,
+                "`string` can be used only to derive from `impl {trait_ident}<str>`"
+            );
+        }
+    }
+
+    let build = |this: &Type, rhs: &Type, is_swapped: bool, a_expr, b_expr| {
+        let body = if is_ord {
+            let reverse = if is_swapped {
+                quote!(.map(::core::cmp::Ordering::reverse))
+            } else {
+                quote!()
+            };
+            quote! {
+                fn partial_cmp(&self, other: &#rhs) -> ::core::option::Option<::core::cmp::Ordering> {
+                    <#a as #trait_<#b>>::partial_cmp(#a_expr, #b_expr)#reverse
+                }
+            }
+        } else {
+            quote! {
+                fn eq(&self, other: &#rhs) -> bool {
+                    <#a as #trait_<#b>>::eq(#a_expr, #b_expr)
+                }
+            }
+        };
+        quote! {
+            #[automatically_derived]
+            impl #impl_g #trait_<#rhs> for #this #where_g {
+                #body
+            }
+        }
+    };
+
+    let mut ts = TokenStream::new();
+    if !args.items.is_empty() {
+        let ref_a = &ref_type(a);
+        let ref_b = &ref_type(b);
+        ts.extend(build(a, ref_b, false, quote!(self), quote!(*other)));
+        // If `B` contains generic parameters, the other implementations may conflict
+        // with `impl PartialEq<&B> for &A` in `core` or violate the orphan rules.
+        if !GenericParamSet::new(&item_impl.generics).contains_in_type(b) {
+            ts.extend(build(ref_a, b, false, quote!(*self), quote!(other)));
+            if a != b {
+                ts.extend(build(b, a, true, quote!(other), quote!(self)));
+                ts.extend(build(ref_b, a, true, quote!(other), quote!(*self)));
+                ts.extend(build(b, ref_a, true, quote!(*other), quote!(self)));
+                if string.is_some() {
+                    let string = &parse_quote!(::std::string::String);
+                    ts.extend(build(
+                        a,
+                        string,
+                        false,
+                        quote!(self),
+                        quote!(other.as_str()),
+                    ));
+                    ts.extend(build(string, a, true, quote!(other), quote!(self.as_str())));
+                }
+            }
+        }
+    }
+    if args.dump {
+        bail!(_, "{}", format!("dump:\n{ts}"));
+    }
+    Ok(ts)
+}
+fn build_by_deref(attr: TokenStream, item_impl: &ItemImpl) -> Result<TokenStream> {
+    let args = parse_arg_list(attr, &["AsRef", "Borrow"])?;
+    let this = &item_impl.self_ty;
+    let g = expand_self(&item_impl.generics, this);
+    let (impl_g, _, where_g) = &g.split_for_impl();
+    let target = expand_self(find_assoc_type(item_impl, "Target")?, this);
+
+    let mut ts = TokenStream::new();
+    for item in &args.items {
+        let (trait_, func) = match item.ident.to_string().as_str() {
+            "AsRef" => (quote!(::core::convert::AsRef), quote!(as_ref)),
+            _ => (quote!(::core::borrow::Borrow), quote!(borrow)),
+        };
+        ts.extend(quote! {
+            #[automatically_derived]
+            impl #impl_g #trait_<#target> for #this #where_g {
+                fn #func(&self) -> &#target {
+                    <#this as ::core::ops::Deref>::deref(self)
+                }
+            }
+        });
+    }
+    if args.dump {
+        bail!(_, "{}", format!("dump:\n{ts}"));
+    }
+    Ok(ts)
+}
+fn build_by_display(attr: TokenStream, item_impl: &ItemImpl) -> Result<TokenStream> {
+    let args = parse_arg_list(attr, &["Debug"])?;
+    let this = &item_impl.self_ty;
+    let g = expand_self(&item_impl.generics, this);
+    let (impl_g, _, where_g) = &g.split_for_impl();
+
+    let mut ts = TokenStream::new();
+    for item in &args.items {
+        let (quoted, type_name) = match item.args() {
+            Some(args) => (args.quoted.value(), args.type_name.value()),
+            None => (false, false),
+        };
+        let body = if type_name {
+            let Type::Path(tp) = this.as_ref() else {
+                bail!(this.span(), "`type_name` requires a type path");
+            };
+            let name = tp.path.segments.last().unwrap().ident.to_string();
+            let quoted_ty = build_quoted_display();
+            quote! {
+                #quoted_ty
+                f.debug_tuple(#name)
+                    .field(&__DeriveExQuoted(self))
+                    .finish()
+            }
+        } else if quoted {
+            let quoted_ty = build_quoted_display();
+            quote! {
+                #quoted_ty
+                ::core::fmt::Debug::fmt(&__DeriveExQuoted(self), f)
+            }
+        } else {
+            quote!(::core::fmt::Display::fmt(self, f))
+        };
+        ts.extend(quote! {
+            #[automatically_derived]
+            impl #impl_g ::core::fmt::Debug for #this #where_g {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    #body
+                }
+            }
+        });
+    }
+    if args.dump {
+        bail!(_, "{}", format!("dump:\n{ts}"));
+    }
+    Ok(ts)
+}
+// Formats `Display` output in the same way as `<str as Debug>::fmt` without allocating a `String`.
+fn build_quoted_display() -> TokenStream {
+    quote! {
+        struct __DeriveExQuoted<'a, T: ?::core::marker::Sized>(&'a T);
+        impl<T: ?::core::marker::Sized + ::core::fmt::Display> ::core::fmt::Debug
+            for __DeriveExQuoted<'_, T>
+        {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                struct __DeriveExEscape<'a, 'b>(&'a mut ::core::fmt::Formatter<'b>);
+                impl ::core::fmt::Write for __DeriveExEscape<'_, '_> {
+                    fn write_str(&mut self, s: &str) -> ::core::fmt::Result {
+                        for c in s.chars() {
+                            // `<str as Debug>::fmt` does not escape single quotes.
+                            if c == '\'' {
+                                ::core::fmt::Write::write_char(self.0, c)?;
+                            } else {
+                                ::core::fmt::Display::fmt(&c.escape_debug(), self.0)?;
+                            }
+                        }
+                        ::core::result::Result::Ok(())
+                    }
+                }
+                ::core::fmt::Write::write_char(f, '"')?;
+                ::core::fmt::write(
+                    &mut __DeriveExEscape(f),
+                    ::core::format_args!("{}", self.0),
+                )?;
+                ::core::fmt::Write::write_char(f, '"')
+            }
+        }
+    }
+}
+fn build_by_inherent_impl(attr: TokenStream, item_impl: &ItemImpl) -> Result<TokenStream> {
+    let args = parse_arg_list(attr, &["Default", "From", "FromStr"])?;
+    let this = &item_impl.self_ty;
+    let g = expand_self(&item_impl.generics, this);
+    let (impl_g, _, where_g) = &g.split_for_impl();
+
+    let mut ts = TokenStream::new();
+    for item in &args.items {
+        let trait_name = item.ident.to_string();
+        let (func, arg_count) = match trait_name.as_str() {
+            "FromStr" => ("parse", 1),
+            "From" => ("new", 1),
+            _ => ("new", 0),
+        };
+        let sig = find_inherent_fn(item_impl, func, arg_count, &trait_name)?;
+        let func = &sig.ident;
+        if trait_name != "FromStr" {
+            let span = match &sig.output {
+                ReturnType::Type(_, ty) if is_self_type(ty, this) => None,
+                ReturnType::Type(_, ty) => Some(ty.span()),
+                ReturnType::Default => Some(sig.paren_token.span.close()),
+            };
+            if let Some(span) = span {
+                bail!(
+                    span,
+                    "`fn {func}` must return `Self` to derive `{trait_name}`"
+                );
+            }
+        }
+        ts.extend(match trait_name.as_str() {
+            "Default" => quote! {
+                #[automatically_derived]
+                impl #impl_g ::core::default::Default for #this #where_g {
+                    fn default() -> Self {
+                        <#this>::#func()
+                    }
+                }
+            },
+            "From" => {
+                let FnArg::Typed(arg) = &sig.inputs[0] else {
+                    unreachable!()
+                };
+                let arg_ty = expand_self(arg.ty.as_ref(), this);
+                quote! {
+                    #[automatically_derived]
+                    impl #impl_g ::core::convert::From<#arg_ty> for #this #where_g {
+                        fn from(value: #arg_ty) -> Self {
+                            <#this>::#func(value)
+                        }
+                    }
+                }
+            }
+            _ => {
+                let message = "expected `fn parse(s: &str) -> Result<Self, E>`";
+                let FnArg::Typed(arg) = &sig.inputs[0] else {
+                    unreachable!()
+                };
+                if !is_str_ref(&arg.ty) {
+                    bail!(arg.ty.span(), "{message}");
+                }
+                let Some((ok_ty, err_ty)) = to_result_types(&sig.output) else {
+                    bail!(sig.output.span(), "{message}");
+                };
+                if !is_self_type(ok_ty, this) {
+                    bail!(ok_ty.span(), "{message}");
+                }
+                let err_ty = expand_self(err_ty, this);
+                quote! {
+                    #[automatically_derived]
+                    impl #impl_g ::core::str::FromStr for #this #where_g {
+                        type Err = #err_ty;
+                        fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                            <#this>::#func(s)
+                        }
+                    }
+                }
+            }
+        });
+    }
+    if args.dump {
+        bail!(_, "{}", format!("dump:\n{ts}"));
+    }
+    Ok(ts)
+}
+fn find_inherent_fn<'a>(
+    item_impl: &'a ItemImpl,
+    name: &str,
+    arg_count: usize,
+    trait_name: &str,
+) -> Result<&'a Signature> {
+    for item in &item_impl.items {
+        if let ImplItem::Fn(f) = item {
+            let sig = &f.sig;
+            if sig.ident != name {
+                continue;
+            }
+            if sig.receiver().is_some() || sig.inputs.len() != arg_count {
+                bail!(
+                    sig.span(),
+                    "`fn {name}` must have {arg_count} argument(s) and no `self` to derive `{trait_name}`"
+                );
+            }
+            if !sig.generics.params.is_empty() {
+                bail!(
+                    sig.generics.span(),
+                    "`fn {name}` must not have generic parameters to derive `{trait_name}`"
+                );
+            }
+            for arg in &sig.inputs {
+                if let FnArg::Typed(arg) = arg {
+                    if let Some(span) = find_impl_trait(&arg.ty) {
+                        bail!(
+                            span,
+                            "`fn {name}` must not have `impl Trait` arguments to derive `{trait_name}`"
+                        );
+                    }
+                }
+            }
+            if let Some(unsafety) = &sig.unsafety {
+                bail!(
+                    unsafety.span(),
+                    "`fn {name}` must not be `unsafe` to derive `{trait_name}`"
+                );
+            }
+            if let Some(asyncness) = &sig.asyncness {
+                bail!(
+                    asyncness.span(),
+                    "`fn {name}` must not be `async` to derive `{trait_name}`"
+                );
+            }
+            return Ok(sig);
+        }
+    }
+    bail!(_, "cannot find `fn {name}` to derive `{trait_name}`");
+}
+fn to_result_types(output: &ReturnType) -> Option<(&Type, &Type)> {
+    if let ReturnType::Type(_, ty) = output {
+        if let Type::Path(tp) = ty.as_ref() {
+            let s = tp.path.segments.last()?;
+            if s.ident == "Result" {
+                if let PathArguments::AngleBracketed(args) = &s.arguments {
+                    if args.args.len() == 2 {
+                        if let (GenericArgument::Type(ok), GenericArgument::Type(err)) =
+                            (&args.args[0], &args.args[1])
+                        {
+                            return Some((ok, err));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+fn is_self_type(ty: &Type, this: &Type) -> bool {
+    let ty = expand_self(ty, this);
+    quote!(#ty).to_string() == quote!(#this).to_string()
+}
+fn is_str_ref(ty: &Type) -> bool {
+    if let Type::Reference(r) = ty {
+        if r.mutability.is_none() {
+            if let Type::Path(tp) = r.elem.as_ref() {
+                return tp.qself.is_none() && tp.path.is_ident("str");
+            }
+        }
+    }
+    false
+}
+fn find_impl_trait(ty: &Type) -> Option<Span> {
+    struct ImplTraitVisitor(Option<Span>);
+    impl Visit<'_> for ImplTraitVisitor {
+        fn visit_type_impl_trait(&mut self, i: &syn::TypeImplTrait) {
+            self.0.get_or_insert(i.span());
+        }
+    }
+    let mut v = ImplTraitVisitor(None);
+    v.visit_type(ty);
+    v.0
+}
+fn parse_arg_list(attr: TokenStream, expected: &[&str]) -> Result<ArgList> {
+    let args: ArgList = parse2(attr)?;
+    for item in &args.items {
+        if !expected.iter().any(|e| item.ident == e) {
+            let expected: Vec<_> = expected.iter().map(|e| format!("`{e}`")).collect();
+            let expected = match expected.split_last() {
+                Some((last, rest)) if !rest.is_empty() => {
+                    format!("{} or {last}", rest.join(", "))
+                }
+                _ => expected.join(""),
+            };
+            bail!(item.ident.span(), "expected {expected}");
+        }
+        if let Some(args) = item.args() {
+            if let Some(reciprocal) = &args.reciprocal {
+                bail!(
+                    reciprocal.name_span,
+                    "`reciprocal = ...` can be used only to derive `Div` from `impl Mul`"
+                );
+            }
+        }
+        verify_debug_args(item)?;
+        verify_string_arg(item)?;
+    }
+    Ok(args)
+}
+fn verify_debug_args(item: &ArgItem) -> Result<()> {
+    if let Some(args) = item.args() {
+        if item.ident != "Debug" {
+            for (name, flag) in [("quoted", &args.quoted), ("type_name", &args.type_name)] {
+                if let Some(span) = flag.span {
+                    bail!(
+                        span,
+                        "`{name}` can be used only to derive `Debug` from `impl Display`"
+                    );
+                }
+            }
+        }
+        if let (Some(_), Some(span)) = (args.quoted.span, args.type_name.span) {
+            bail!(span, "`quoted` and `type_name` cannot be used together");
+        }
+    }
+    Ok(())
+}
+fn verify_string_arg(item: &ArgItem) -> Result<()> {
+    if let Some(span) = item.args().and_then(|args| args.string.span) {
+        if item.ident != "PartialEq" && item.ident != "PartialOrd" {
+            bail!(
+                span,
+                "`string` can be used only to derive from `impl PartialEq<str>` or `impl PartialOrd<str>`"
+            );
+        }
+    }
+    Ok(())
+}
 fn find_output_type(item_impl: &ItemImpl) -> Result<&Type> {
+    find_assoc_type(item_impl, "Output")
+}
+fn find_assoc_type<'a>(item_impl: &'a ItemImpl, name: &str) -> Result<&'a Type> {
     for item in &item_impl.items {
         if let ImplItem::Type(t) = item {
-            if t.ident == "Output" {
+            if t.ident == name {
                 return Ok(&t.ty);
             }
         }
     }
-    bail!(_, "cannot find associate type `Output`");
+    bail!(_, "cannot find associate type `{name}`");
 }
 fn to_ref_elem(ty: &Type) -> (Type, bool) {
     if let Type::Reference(tr) = ty {
-        if tr.lifetime.is_none() && tr.mutability.is_none() {
+        if tr.mutability.is_none() {
             return (tr.elem.as_ref().clone(), true);
         }
     }
     (ty.clone(), false)
 }
+
+// Removes the lifetime parameters used only by the references of `Self` and `Rhs`,
+// so that the generated implementations can use fresh lifetimes.
+// Lifetimes that are only referenced by the bounds of removed lifetimes are also removed.
+fn remove_ref_lifetimes(item_impl: &ItemImpl, types: &[&Type]) -> Result<Generics> {
+    struct LifetimeVisitor(HashSet<Lifetime>);
+    impl Visit<'_> for LifetimeVisitor {
+        fn visit_lifetime(&mut self, i: &Lifetime) {
+            self.0.insert(i.clone());
+        }
+    }
+    let self_ty = &item_impl.self_ty;
+    let mut generics = expand_self(&item_impl.generics, self_ty);
+    let mut v = LifetimeVisitor(HashSet::new());
+    let mut ref_lifetimes = Vec::new();
+    for ty in types {
+        let (elem, is_ref) = to_ref_elem(ty);
+        if let (true, Type::Reference(tr)) = (is_ref, ty) {
+            if let Some(lt) = &tr.lifetime {
+                if lt.ident != "_" {
+                    ref_lifetimes.push(lt);
+                }
+            }
+        }
+        v.visit_type(&elem);
+    }
+    for item in &item_impl.items {
+        if let ImplItem::Type(t) = item {
+            v.visit_type(&expand_self(&t.ty, self_ty));
+        }
+    }
+    let mut outlives = Vec::new();
+    for p in &generics.params {
+        match p {
+            GenericParam::Lifetime(p) => outlives.push((&p.lifetime, &p.bounds)),
+            GenericParam::Type(p) => v.visit_type_param(p),
+            GenericParam::Const(p) => v.visit_const_param(p),
+        }
+    }
+    if let Some(wc) = &generics.where_clause {
+        for p in &wc.predicates {
+            match p {
+                WherePredicate::Lifetime(p) => outlives.push((&p.lifetime, &p.bounds)),
+                _ => v.visit_where_predicate(p),
+            }
+        }
+    }
+    loop {
+        let len = v.0.len();
+        for (lt, bounds) in &outlives {
+            if v.0.contains(lt) {
+                bounds.iter().for_each(|b| v.visit_lifetime(b));
+            }
+        }
+        if v.0.len() == len {
+            break;
+        }
+    }
+    let used = v.0;
+    for lt in ref_lifetimes {
+        let is_param = generics
+            .lifetimes()
+            .any(|p| p.lifetime == *lt && !used.contains(lt));
+        if !is_param {
+            bail!(
+                lt.span(),
+                "the lifetime of the reference must be a lifetime parameter of the impl that is not used elsewhere"
+            );
+        }
+    }
+    generics.params = generics
+        .params
+        .into_iter()
+        .filter(|p| match p {
+            GenericParam::Lifetime(p) => used.contains(&p.lifetime),
+            _ => true,
+        })
+        .collect();
+    if let Some(wc) = &mut generics.where_clause {
+        wc.predicates = wc
+            .predicates
+            .clone()
+            .into_iter()
+            .filter(|p| match p {
+                WherePredicate::Lifetime(p) => used.contains(&p.lifetime),
+                _ => true,
+            })
+            .collect();
+    }
+    Ok(generics)
+}
 fn to_rhs(s: &PathSegment, self_ty: &Type) -> Type {
     if let PathArguments::AngleBracketed(args) = &s.arguments {
         if args.args.len() == 1 {
@@ -1,25 +1,35 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote, quote_spanned, ToTokens};
 use structmeta::{Flag, NameArgs, NameValue, Parse, StructMeta};
 use syn::{
-    parse::Parse, parse2, parse_quote, spanned::Spanned, token, Attribute, Data, DataEnum,
-    DataStruct, DeriveInput, Error, Expr, ExprLit, Field, Fields, Ident, Index, ItemEnum,
-    ItemStruct, Lit, Meta, Path, Result, Type, Variant,
+    bracketed,
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    parse2, parse_quote,
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token, Attribute, Data, DataEnum, DataStruct, DataUnion, DeriveInput, Error, Expr, ExprLit,
+    Field, Fields, Generics, Ident, Index, Item, ItemEnum, ItemMod, ItemStruct, ItemUnion, Lit,
+    LitBool, LitInt, LitStr, Meta, Path, PathArguments, Result, Token, Type, TypePath, Variant,
 };
 
 use crate::{
     bound::{Bound, Bounds, WhereClauseBuilder},
-    common::BinaryOp,
+    common::{BinaryOp, OverflowMode, UnaryOp},
     syn_utils::expand_self,
 };
 
-use self::compare_op::{
-    build_compare_op_for_enum, build_compare_op_for_struct, HelperAttributesForCompareOp,
+use self::{
+    compare_op::{
+        build_compare_op_for_enum, build_compare_op_for_struct, HelperAttributesForCompareOp,
+    },
+    rename_rule::RenameRule,
 };
 
 mod compare_op;
+mod rename_rule;
 
 #[derive(StructMeta, Debug)]
 #[struct_meta(name_filter = "snake_case")]
@@ -27,6 +37,8 @@ struct Args {
     #[struct_meta(unnamed)]
     items: Vec<DeriveItem>,
     bound: Option<NameArgs<Vec<Bound>>>,
+    refs: Option<NameValue<ArgsForRefs>>,
+    skip: Flag,
     dump: bool,
 }
 
@@ -40,6 +52,8 @@ struct DeriveItem {
 #[struct_meta(name_filter = "snake_case")]
 struct DeriveItemArgs {
     bound: Option<NameArgs<Vec<Bound>>>,
+    refs: Option<NameValue<ArgsForRefs>>,
+    display: Flag,
     dump: bool,
 }
 
@@ -54,17 +68,34 @@ enum DeriveItemArgsOption {
     None,
 }
 
+#[derive(Debug)]
+struct ArgsForRefs(Vec<Ident>);
+
+impl Parse for ArgsForRefs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(token::Bracket) {
+            let content;
+            bracketed!(content in input);
+            let items = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+            Ok(Self(items.into_iter().collect()))
+        } else {
+            Ok(Self(vec![input.parse()?]))
+        }
+    }
+}
+
 pub fn build_derive(input: TokenStream) -> Result<TokenStream> {
     build_from_derive_input(parse2(input)?)
 }
 fn build_from_derive_input(item: DeriveInput) -> Result<TokenStream> {
     let mut kinds = HelperAttributeKinds::new(true);
+    let es = DeriveEntry::from_root(None, &item.attrs)?;
     match &item.data {
         Data::Struct(data) => {
-            build_by_item_struct_core(None, &to_item_struct(&item, data), &mut kinds)
+            build_by_item_struct_core(es, &to_item_struct(&item, data), &mut kinds)
         }
-        Data::Enum(data) => build_by_item_enum_core(None, &to_item_enum(&item, data), &mut kinds),
-        Data::Union(_) => bail!(Span::call_site(), "does not support union types"),
+        Data::Enum(data) => build_by_item_enum_core(es, &to_item_enum(&item, data), &mut kinds),
+        Data::Union(data) => build_by_item_union_core(es, &to_item_union(&item, data), &mut kinds),
     }
 }
 fn to_item_struct(item: &DeriveInput, data: &DataStruct) -> ItemStruct {
@@ -78,6 +109,16 @@ fn to_item_struct(item: &DeriveInput, data: &DataStruct) -> ItemStruct {
         semi_token: data.semi_token,
     }
 }
+fn to_item_union(item: &DeriveInput, data: &DataUnion) -> ItemUnion {
+    ItemUnion {
+        attrs: item.attrs.clone(),
+        vis: item.vis.clone(),
+        union_token: data.union_token,
+        ident: item.ident.clone(),
+        generics: item.generics.clone(),
+        fields: data.fields.clone(),
+    }
+}
 fn to_item_enum(item: &DeriveInput, data: &DataEnum) -> ItemEnum {
     ItemEnum {
         attrs: item.attrs.clone(),
@@ -91,8 +132,15 @@ fn to_item_enum(item: &DeriveInput, data: &DataEnum) -> ItemEnum {
 }
 
 pub fn build_by_item_struct(attr: TokenStream, item: &mut ItemStruct) -> Result<TokenStream> {
+    let es = DeriveEntry::from_root(Some(attr), &item.attrs);
+    build_by_item_struct_with(es, item)
+}
+fn build_by_item_struct_with(
+    es: Result<Vec<DeriveEntry>>,
+    item: &mut ItemStruct,
+) -> Result<TokenStream> {
     let mut kinds = HelperAttributeKinds::new(true);
-    let result = build_by_item_struct_core(Some(attr), item, &mut kinds);
+    let result = es.and_then(|es| build_by_item_struct_core(es, item, &mut kinds));
     remove_attrs(&mut item.attrs, &kinds);
     for field in &mut item.fields {
         remove_attrs(&mut field.attrs, &kinds)
@@ -101,11 +149,10 @@ pub fn build_by_item_struct(attr: TokenStream, item: &mut ItemStruct) -> Result<
 }
 
 fn build_by_item_struct_core(
-    attr: Option<TokenStream>,
+    es: Vec<DeriveEntry>,
     item: &ItemStruct,
     kinds: &mut HelperAttributeKinds,
 ) -> Result<TokenStream> {
-    let es = DeriveEntry::from_root(attr, &item.attrs)?;
     kinds.extend(&es);
     let hattrs = HelperAttributes::from_attrs(
         &item.attrs,
@@ -116,9 +163,12 @@ fn build_by_item_struct_core(
     let mut ts_all = TokenStream::new();
     for e in es {
         let result = match e.kind {
-            DeriveItemKind::BinaryOp(op) => build_binary_op(item, op, &e, &fields),
-            DeriveItemKind::AssignOp(op) => build_assign_op(item, op, &e, &fields),
-            DeriveItemKind::UnaryOp(op) => build_unary_op(item, op, &e, &fields),
+            DeriveItemKind::BinaryOp(op) => build_binary_op(item, op, &e, &hattrs, &fields),
+            DeriveItemKind::AssignOp(op) => build_assign_op(item, op, &e, &hattrs, &fields),
+            DeriveItemKind::UnaryOp(op) => {
+                build_unary_op_for_struct(item, op, &e, &hattrs, &fields)
+            }
+            DeriveItemKind::OverflowOp(mode, op) => build_overflow_op(item, mode, op, &e, &fields),
             DeriveItemKind::CompareOp(op) => {
                 build_compare_op_for_struct(op, item, &e, &hattrs, &fields)
             }
@@ -135,8 +185,15 @@ fn build_by_item_struct_core(
     Ok(ts_all)
 }
 pub fn build_by_item_enum(attr: TokenStream, item: &mut ItemEnum) -> Result<TokenStream> {
+    let es = DeriveEntry::from_root(Some(attr), &item.attrs);
+    build_by_item_enum_with(es, item)
+}
+fn build_by_item_enum_with(
+    es: Result<Vec<DeriveEntry>>,
+    item: &mut ItemEnum,
+) -> Result<TokenStream> {
     let mut kinds = HelperAttributeKinds::new(true);
-    let result = build_by_item_enum_core(Some(attr), item, &mut kinds);
+    let result = es.and_then(|es| build_by_item_enum_core(es, item, &mut kinds));
     remove_attrs(&mut item.attrs, &kinds);
     for variant in &mut item.variants {
         remove_attrs(&mut variant.attrs, &kinds);
@@ -147,11 +204,10 @@ pub fn build_by_item_enum(attr: TokenStream, item: &mut ItemEnum) -> Result<Toke
     result
 }
 fn build_by_item_enum_core(
-    attr: Option<TokenStream>,
+    es: Vec<DeriveEntry>,
     item: &ItemEnum,
     kinds: &mut HelperAttributeKinds,
 ) -> Result<TokenStream> {
-    let es = DeriveEntry::from_root(attr, &item.attrs)?;
     kinds.extend(&es);
     let hattrs = HelperAttributes::from_attrs(
         &item.attrs,
@@ -162,6 +218,9 @@ fn build_by_item_enum_core(
     let mut ts_all = TokenStream::new();
     for e in es {
         let result = match e.kind {
+            DeriveItemKind::UnaryOp(op) => {
+                build_unary_op_for_enum(item, op, &e, &hattrs, &variants)
+            }
             DeriveItemKind::CompareOp(op) => {
                 build_compare_op_for_enum(op, item, &e, &hattrs, &variants)
             }
@@ -176,10 +235,91 @@ fn build_by_item_enum_core(
     Ok(ts_all)
 }
 
+pub fn build_by_item_union(attr: TokenStream, item: &mut ItemUnion) -> Result<TokenStream> {
+    let es = DeriveEntry::from_root(Some(attr), &item.attrs);
+    build_by_item_union_with(es, item)
+}
+fn build_by_item_union_with(
+    es: Result<Vec<DeriveEntry>>,
+    item: &mut ItemUnion,
+) -> Result<TokenStream> {
+    let mut kinds = HelperAttributeKinds::new(true);
+    let result = es.and_then(|es| build_by_item_union_core(es, item, &mut kinds));
+    remove_attrs(&mut item.attrs, &kinds);
+    for field in &mut item.fields.named {
+        remove_attrs(&mut field.attrs, &kinds)
+    }
+    result
+}
+fn build_by_item_union_core(
+    es: Vec<DeriveEntry>,
+    item: &ItemUnion,
+    kinds: &mut HelperAttributeKinds,
+) -> Result<TokenStream> {
+    kinds.extend(&es);
+    let hattrs = HelperAttributes::from_attrs(
+        &item.attrs,
+        AttributeTarget::Union,
+        &kinds.without_derive_ex(),
+    )?;
+    for field in &item.fields.named {
+        if let Some(attr) = field.attrs.iter().find(|a| a.path().is_ident("debug")) {
+            bail!(
+                attr.span(),
+                "`#[debug]` cannot be specified for union fields"
+            );
+        }
+    }
+    let mut ts_all = TokenStream::new();
+    for e in es {
+        let result = match e.kind {
+            DeriveItemKind::Debug => build_debug_for_union(item, &e, &hattrs),
+            _ => bail!(e.span, "derive `{}` for union is not supported", e.kind),
+        };
+        ts_all.extend(e.apply_dump(result));
+    }
+    Ok(ts_all)
+}
+
+pub fn build_by_item_mod(attr: TokenStream, item: &mut ItemMod) -> Result<TokenStream> {
+    let args: Args = parse2(attr.clone())?;
+    if let Some(span) = args.skip.span {
+        bail!(span, "`skip` cannot be used for `mod`");
+    }
+    let Some((_, items)) = &mut item.content else {
+        bail!(
+            item.span(),
+            "`#[derive_ex]` can be specified only for inline `mod`"
+        );
+    };
+    let mut ts_all = TokenStream::new();
+    for item in items.iter_mut() {
+        let result = match item {
+            Item::Struct(item) => {
+                build_by_item_struct_with(DeriveEntry::from_mod(&attr, &item.attrs, |_| true), item)
+            }
+            Item::Enum(item) => {
+                build_by_item_enum_with(DeriveEntry::from_mod(&attr, &item.attrs, |_| true), item)
+            }
+            Item::Union(item) => {
+                // Only the traits that can be derived for unions are applied from the module.
+                let es =
+                    DeriveEntry::from_mod(&attr, &item.attrs, |kind| kind == DeriveItemKind::Debug);
+                build_by_item_union_with(es, item)
+            }
+            _ => continue,
+        };
+        ts_all.extend(result.unwrap_or_else(|e| e.to_compile_error()));
+    }
+    items.push(Item::Verbatim(ts_all));
+    Ok(TokenStream::new())
+}
+
 fn build_binary_op(
     item: &ItemStruct,
     op: BinaryOp,
     e: &DeriveEntry,
+    hattrs: &HelperAttributes,
     fields: &[FieldEntry],
 ) -> Result<TokenStream> {
     let kind = DeriveItemKind::BinaryOp(op);
@@ -195,16 +335,50 @@ fn build_binary_op(
         let self_ty = with_ref(&this_ty, lhs_is_ref);
         let rhs_ty = with_ref(&this_ty, rhs_is_ref);
         let mut wcb = WhereClauseBuilder::new(&generics);
-        let use_bounds = e.push_bounds_to(&mut wcb);
+        let use_bounds = e.push_bounds_to_with(hattrs, kind, &mut wcb);
         let mut values = Vec::new();
         for field in fields {
             let field_ty = &field.field.ty;
+            if let Some(by) = field.hattrs.ops.by(kind) {
+                let fn_ident = field.make_ident(&format!("__{}", by.kind.to_attr_name()));
+                let lhs = clone_with(&member(quote!(self), field), field_ty, lhs_is_ref);
+                let rhs = clone_with(&member(quote!(rhs), field), field_ty, rhs_is_ref);
+                let by = &by.expr;
+                values.push(quote! {
+                    {
+                        fn #fn_ident<L, R, O>(lhs: L, rhs: R, f: impl ::core::ops::FnOnce(L, R) -> O) -> O {
+                            f(lhs, rhs)
+                        }
+                        #fn_ident(#lhs, #rhs, #by)
+                    }
+                });
+                if field.hattrs.push_bounds_to(use_bounds, kind, &mut wcb)
+                    && (lhs_is_ref || rhs_is_ref)
+                {
+                    wcb.push_bounds_for_field_with(field.field, &DeriveItemKind::Clone.to_path());
+                }
+                continue;
+            }
             let lhs = with_ref(&member(quote!(self), field), lhs_is_ref);
             let rhs = with_ref(&member(quote!(rhs), field), rhs_is_ref);
-            let lhs_ty = with_ref(field_ty, lhs_is_ref);
-            let rhs_ty = with_ref(field_ty, rhs_is_ref);
-            values.push(quote!(<#lhs_ty as #trait_<#rhs_ty>>::#func_name(#lhs, #rhs)));
-            field.push_bounds_to(use_bounds, kind, &mut wcb);
+            let mut leaves = Vec::new();
+            values.push(build_elementwise(
+                field_ty,
+                &[lhs, rhs],
+                ElementwiseMode::Value,
+                0,
+                &|ty, args| {
+                    let lhs_ty = with_ref(ty, lhs_is_ref);
+                    let rhs_ty = with_ref(ty, rhs_is_ref);
+                    quote!(<#lhs_ty as #trait_<#rhs_ty>>::#func_name(#(#args),*))
+                },
+                &mut leaves,
+            ));
+            if field.hattrs.push_bounds_to(use_bounds, kind, &mut wcb) {
+                for ty in &leaves {
+                    wcb.push_bounds_for_type(ty);
+                }
+            }
         }
         let ctor_args = build_ctor_args(&item.fields, &values);
         let wheres = wcb.build(|ty| match (lhs_is_ref, rhs_is_ref) {
@@ -226,7 +400,9 @@ fn build_binary_op(
     let mut ts = TokenStream::new();
     for lhs_is_ref in [false, true] {
         for rhs_is_ref in [false, true] {
-            ts.extend(build(lhs_is_ref, rhs_is_ref));
+            if e.use_refs(RefKind::new(lhs_is_ref, rhs_is_ref)) {
+                ts.extend(build(lhs_is_ref, rhs_is_ref));
+            }
         }
     }
     Ok(ts)
@@ -235,6 +411,7 @@ fn build_assign_op(
     item: &ItemStruct,
     op: BinaryOp,
     e: &DeriveEntry,
+    hattrs: &HelperAttributes,
     fields: &[FieldEntry],
 ) -> Result<TokenStream> {
     let kind = DeriveItemKind::AssignOp(op);
@@ -249,15 +426,60 @@ fn build_assign_op(
     let build = |rhs_is_ref: bool| {
         let rhs_ty = with_ref(&this_ty, rhs_is_ref);
         let mut wcb = WhereClauseBuilder::new(&generics);
-        let use_bounds = e.push_bounds_to(&mut wcb);
+        let use_bounds = e.push_bounds_to_with(hattrs, kind, &mut wcb);
         let mut exprs = Vec::new();
         for field in fields {
             let field_ty = &field.field.ty;
             let lhs = member(quote!(self), field);
+            if let Some(by) = field.hattrs.ops.by(kind) {
+                let fn_ident = field.make_ident(&format!("__{}", by.kind.to_attr_name()));
+                let rhs = clone_with(&member(quote!(rhs), field), field_ty, rhs_is_ref);
+                let by_expr = &by.expr;
+                let use_clone = if by.kind == kind {
+                    exprs.push(quote! {
+                        {
+                            fn #fn_ident<L, R>(lhs: &mut L, rhs: R, f: impl ::core::ops::FnOnce(&mut L, R)) {
+                                f(lhs, rhs)
+                            }
+                            #fn_ident(&mut #lhs, #rhs, #by_expr)
+                        }
+                    });
+                    rhs_is_ref
+                } else {
+                    let lhs_value = clone_with(&lhs, field_ty, true);
+                    exprs.push(quote! {
+                        {
+                            fn #fn_ident<L, R, O>(lhs: L, rhs: R, f: impl ::core::ops::FnOnce(L, R) -> O) -> O {
+                                f(lhs, rhs)
+                            }
+                            #lhs = #fn_ident(#lhs_value, #rhs, #by_expr)
+                        }
+                    });
+                    true
+                };
+                if field.hattrs.push_bounds_to(use_bounds, kind, &mut wcb) && use_clone {
+                    wcb.push_bounds_for_field_with(field.field, &DeriveItemKind::Clone.to_path());
+                }
+                continue;
+            }
             let rhs = with_ref(&member(quote!(rhs), field), rhs_is_ref);
-            let rhs_ty = with_ref(field_ty, rhs_is_ref);
-            exprs.push(quote!(<#field_ty as #trait_<#rhs_ty>>::#func_name(&mut #lhs, #rhs)));
-            field.push_bounds_to(use_bounds, kind, &mut wcb);
+            let mut leaves = Vec::new();
+            exprs.push(build_elementwise(
+                field_ty,
+                &[quote!(&mut #lhs), rhs],
+                ElementwiseMode::Assign,
+                0,
+                &|ty, args| {
+                    let rhs_ty = with_ref(ty, rhs_is_ref);
+                    quote!(<#ty as #trait_<#rhs_ty>>::#func_name(#(#args),*))
+                },
+                &mut leaves,
+            ));
+            if field.hattrs.push_bounds_to(use_bounds, kind, &mut wcb) {
+                for ty in &leaves {
+                    wcb.push_bounds_for_type(ty);
+                }
+            }
         }
         let wheres = wcb.build(|ty| match rhs_is_ref {
             true => parse_quote!(for<'a> #ty : #trait_<&'a #ty>),
@@ -274,14 +496,17 @@ fn build_assign_op(
     };
     let mut ts = TokenStream::new();
     for rhs_is_ref in [false, true] {
-        ts.extend(build(rhs_is_ref));
+        if e.use_refs(RefKind::new(false, rhs_is_ref)) {
+            ts.extend(build(rhs_is_ref));
+        }
     }
     Ok(ts)
 }
-fn build_unary_op(
+fn build_unary_op_for_struct(
     item: &ItemStruct,
     op: UnaryOp,
     e: &DeriveEntry,
+    hattrs: &HelperAttributes,
     fields: &[FieldEntry],
 ) -> Result<TokenStream> {
     let kind = DeriveItemKind::UnaryOp(op);
@@ -296,14 +521,13 @@ fn build_unary_op(
     let build = |lhs_is_ref: bool| {
         let self_ty = with_ref(&this_ty, lhs_is_ref);
         let mut wcb = WhereClauseBuilder::new(&generics);
-        let use_bounds = e.push_bounds_to(&mut wcb);
+        let use_bounds = e.push_bounds_to_with(hattrs, kind, &mut wcb);
         let mut values = Vec::new();
         for field in fields {
-            let field_ty = &field.field.ty;
-            let lhs = with_ref(&member(quote!(self), field), lhs_is_ref);
-            let lhs_ty = with_ref(field_ty, lhs_is_ref);
-            values.push(quote!(<#lhs_ty as #trait_>::#func_name(#lhs)));
-            field.push_bounds_to(use_bounds, kind, &mut wcb);
+            let lhs = member(quote!(self), field);
+            values.push(build_unary_op_value(
+                op, field, &lhs, lhs_is_ref, use_bounds, &mut wcb,
+            ));
         }
         let ctor_args = build_ctor_args(&item.fields, &values);
         let wheres = wcb.build(|ty| match lhs_is_ref {
@@ -322,10 +546,286 @@ fn build_unary_op(
     };
     let mut ts = TokenStream::new();
     for lhs_is_ref in [false, true] {
-        ts.extend(build(lhs_is_ref));
+        if e.use_refs(RefKind::new(lhs_is_ref, false)) {
+            ts.extend(build(lhs_is_ref));
+        }
+    }
+    Ok(ts)
+}
+fn build_unary_op_for_enum(
+    item: &ItemEnum,
+    op: UnaryOp,
+    e: &DeriveEntry,
+    hattrs: &HelperAttributes,
+    variants: &[VariantEntry],
+) -> Result<TokenStream> {
+    let kind = DeriveItemKind::UnaryOp(op);
+    let (_, type_g, _) = item.generics.split_for_impl();
+    let this_ty_ident = &item.ident;
+    let this_ty: Type = parse_quote!(#this_ty_ident #type_g);
+    let generics = expand_self(&item.generics, &this_ty);
+    let (impl_g, _, _) = generics.split_for_impl();
+    let trait_ = kind.to_path();
+    let func_name = format_ident!("{}", op.to_func_name());
+
+    for variant in variants {
+        if let Some(to) = variant.hattrs.ops.to(kind) {
+            if !variant.fields.is_empty() {
+                bail!(
+                    to.span(),
+                    "`to = ...` can be specified only for variants without fields"
+                );
+            }
+            let Some(target) = variants.iter().find(|v| &v.variant.ident == to) else {
+                bail!(to.span(), "variant `{to}` does not exist");
+            };
+            if !target.fields.is_empty() {
+                bail!(to.span(), "variant `{to}` must not have fields");
+            }
+        }
+    }
+
+    let build = |lhs_is_ref: bool| {
+        let self_ty = with_ref(&this_ty, lhs_is_ref);
+        let mut wcb = WhereClauseBuilder::new(&generics);
+        let use_bounds = e.push_bounds_to_with(hattrs, kind, &mut wcb);
+        let mut arms = Vec::new();
+        for variant in variants {
+            let variant_ident = &variant.variant.ident;
+            let use_bounds = variant.hattrs.push_bounds_to(use_bounds, kind, &mut wcb);
+            let pat = variant.make_pat_with_self_path("_l", this_ty_ident);
+            if let Some(to) = variant.hattrs.ops.to(kind) {
+                arms.push(quote!(#pat => #this_ty_ident::#to));
+                continue;
+            }
+            let mut values = Vec::new();
+            for field in &variant.fields {
+                let var = field.make_ident("_l");
+                let lhs = match lhs_is_ref {
+                    true => quote!((*#var)),
+                    false => quote!(#var),
+                };
+                values.push(build_unary_op_value(
+                    op, field, &lhs, lhs_is_ref, use_bounds, &mut wcb,
+                ));
+            }
+            let ctor_args = build_ctor_args(&variant.variant.fields, &values);
+            arms.push(quote!(#pat => #this_ty_ident::#variant_ident #ctor_args));
+        }
+        let wheres = wcb.build(|ty| match lhs_is_ref {
+            true => quote!(for<'a> &'a #ty : #trait_<Output = #ty>),
+            false => quote!(#ty : #trait_<Output = #ty>),
+        });
+        let this = match (lhs_is_ref, variants.is_empty()) {
+            (true, true) => quote!(*self),
+            _ => quote!(self),
+        };
+        quote! {
+            #[automatically_derived]
+            impl #impl_g #trait_ for #self_ty #wheres {
+                type Output = #this_ty;
+                fn #func_name(self) -> Self::Output {
+                    match #this {
+                        #(#arms,)*
+                    }
+                }
+            }
+        }
+    };
+    let mut ts = TokenStream::new();
+    for lhs_is_ref in [false, true] {
+        if e.use_refs(RefKind::new(lhs_is_ref, false)) {
+            ts.extend(build(lhs_is_ref));
+        }
     }
     Ok(ts)
 }
+fn build_unary_op_value(
+    op: UnaryOp,
+    field: &FieldEntry,
+    lhs: &TokenStream,
+    lhs_is_ref: bool,
+    use_bounds: bool,
+    wcb: &mut WhereClauseBuilder,
+) -> TokenStream {
+    let kind = DeriveItemKind::UnaryOp(op);
+    let trait_ = kind.to_path();
+    let func_name = format_ident!("{}", op.to_func_name());
+    let field_ty = &field.field.ty;
+    if let Some(by) = field.hattrs.ops.by(kind) {
+        let fn_ident = field.make_ident(&format!("__{}", by.kind.to_attr_name()));
+        let lhs = clone_with(lhs, field_ty, lhs_is_ref);
+        let by = &by.expr;
+        if field.hattrs.push_bounds_to(use_bounds, kind, wcb) && lhs_is_ref {
+            wcb.push_bounds_for_field_with(field.field, &DeriveItemKind::Clone.to_path());
+        }
+        return quote! {
+            {
+                fn #fn_ident<L, O>(lhs: L, f: impl ::core::ops::FnOnce(L) -> O) -> O {
+                    f(lhs)
+                }
+                #fn_ident(#lhs, #by)
+            }
+        };
+    }
+    let lhs = with_ref(lhs, lhs_is_ref);
+    let mut leaves = Vec::new();
+    let value = build_elementwise(
+        field_ty,
+        &[lhs],
+        ElementwiseMode::Value,
+        0,
+        &|ty, args| {
+            let lhs_ty = with_ref(ty, lhs_is_ref);
+            quote!(<#lhs_ty as #trait_>::#func_name(#(#args),*))
+        },
+        &mut leaves,
+    );
+    if field.hattrs.push_bounds_to(use_bounds, kind, wcb) {
+        for ty in &leaves {
+            wcb.push_bounds_for_type(ty);
+        }
+    }
+    value
+}
+
+fn build_overflow_op(
+    item: &ItemStruct,
+    mode: OverflowMode,
+    op: BinaryOp,
+    e: &DeriveEntry,
+    fields: &[FieldEntry],
+) -> Result<TokenStream> {
+    let kind = DeriveItemKind::OverflowOp(mode, op);
+    let (impl_g, type_g, _) = item.generics.split_for_impl();
+    let this_ty_ident = &item.ident;
+    let this_ty: Type = parse_quote!(#this_ty_ident #type_g);
+    let vis = &item.vis;
+    let func_name = format_ident!("{}_{}", mode.to_str_snake_case(), op.to_func_name());
+
+    let mut wcb = WhereClauseBuilder::new(&item.generics);
+    let use_bounds = e.push_bounds_to(&mut wcb);
+    let mut values = Vec::new();
+    for field in fields {
+        let lhs = member(quote!(self), field);
+        let rhs = member(quote!(rhs), field);
+        values.push(match mode {
+            OverflowMode::Checked => quote!(#lhs.#func_name(#rhs)?),
+            OverflowMode::Wrapping | OverflowMode::Saturating => quote!(#lhs.#func_name(#rhs)),
+        });
+        let _ = field.hattrs.push_bounds_to(use_bounds, kind, &mut wcb);
+    }
+    // Since there is no trait that represents these methods, the type cannot be used as a trait bound.
+    if let Some(ty) = wcb.types().first() {
+        bail!(
+            ty.span(),
+            "`#[derive_ex({kind})]` does not support trait bounds by type. Use `bound(T : Trait)` instead."
+        );
+    }
+    let ctor_args = build_ctor_args(&item.fields, &values);
+    let (output, value) = match mode {
+        OverflowMode::Checked => (
+            quote!(::core::option::Option<Self>),
+            quote!(::core::option::Option::Some(#this_ty_ident #ctor_args)),
+        ),
+        OverflowMode::Wrapping | OverflowMode::Saturating => {
+            (quote!(Self), quote!(#this_ty_ident #ctor_args))
+        }
+    };
+    let doc = format!("Calls `{func_name}` on each field.");
+    let wheres = wcb.build(|_| unreachable!());
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_g #this_ty #wheres {
+            #[doc = #doc]
+            #vis fn #func_name(self, rhs: Self) -> #output {
+                #value
+            }
+        }
+    })
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ElementwiseMode {
+    Value,
+    Assign,
+}
+
+// Apply operators to each element of arrays and tuples,
+// since arrays and tuples do not implement operator traits.
+fn build_elementwise(
+    ty: &Type,
+    args: &[TokenStream],
+    mode: ElementwiseMode,
+    depth: usize,
+    leaf: &dyn Fn(&Type, &[TokenStream]) -> TokenStream,
+    leaves: &mut Vec<Type>,
+) -> TokenStream {
+    match ty {
+        Type::Group(ty) => build_elementwise(&ty.elem, args, mode, depth, leaf, leaves),
+        Type::Paren(ty) => build_elementwise(&ty.elem, args, mode, depth, leaf, leaves),
+        Type::Tuple(ty) if ty.elems.is_empty() => quote!(()),
+        Type::Tuple(ty) => {
+            let mut stmts = Vec::new();
+            let mut vars = Vec::new();
+            for (i, arg) in args.iter().enumerate() {
+                let vs: Vec<_> = (0..ty.elems.len())
+                    .map(|j| format_ident!("_e{}_{}_{}", i, depth, j))
+                    .collect();
+                stmts.push(quote!(let (#(#vs,)*) = #arg;));
+                vars.push(vs);
+            }
+            let mut items = Vec::new();
+            for (j, elem) in ty.elems.iter().enumerate() {
+                let args: Vec<_> = vars.iter().map(|vs| vs[j].to_token_stream()).collect();
+                items.push(build_elementwise(
+                    elem,
+                    &args,
+                    mode,
+                    depth + 1,
+                    leaf,
+                    leaves,
+                ));
+            }
+            match mode {
+                ElementwiseMode::Value => quote!({ #(#stmts)* (#(#items,)*) }),
+                ElementwiseMode::Assign => quote!({ #(#stmts)* #(#items;)* }),
+            }
+        }
+        Type::Array(ty) => {
+            let iters: Vec<_> = (0..args.len())
+                .map(|i| format_ident!("_it{}_{}", i, depth))
+                .collect();
+            let vars: Vec<_> = (0..args.len())
+                .map(|i| format_ident!("_e{}_{}", i, depth))
+                .collect();
+            let elem_args: Vec<_> = vars.iter().map(|v| v.to_token_stream()).collect();
+            let elem = build_elementwise(&ty.elem, &elem_args, mode, depth + 1, leaf, leaves);
+            let stmts = quote! {
+                #(let mut #iters = ::core::iter::IntoIterator::into_iter(#args);)*
+            };
+            match mode {
+                ElementwiseMode::Value => quote!({
+                    #stmts
+                    ::core::array::from_fn(|_| {
+                        #(let #vars = ::core::option::Option::unwrap(::core::iter::Iterator::next(&mut #iters));)*
+                        #elem
+                    })
+                }),
+                ElementwiseMode::Assign => quote!({
+                    #stmts
+                    while let (#(::core::option::Option::Some(#vars),)*) = (#(::core::iter::Iterator::next(&mut #iters),)*) {
+                        #elem;
+                    }
+                }),
+            }
+        }
+        _ => {
+            leaves.push(ty.clone());
+            leaf(ty, args)
+        }
+    }
+}
 
 fn build_clone_for_struct(
     item: &ItemStruct,
@@ -487,29 +987,115 @@ fn build_debug_for_struct(
     let this_ty: Type = parse_quote!(#this_ty_ident #type_g);
     let trait_ = kind.to_path();
 
+    if e.display {
+        hattrs.debug.verify_display()?;
+        for field in fields {
+            field.hattrs.debug.verify_display()?;
+        }
+        return Ok(build_debug_by_display(&item.generics, &this_ty, e, hattrs));
+    }
     let mut wcb = WhereClauseBuilder::new(&item.generics);
     let use_bounds = e.push_bounds_to_with(hattrs, kind, &mut wcb);
     let to_expr = |field: &FieldEntry| {
         let member = field.member();
         quote_spanned!(field.span() => &self.#member)
     };
-    let expr = build_debug_expr(
-        this_ty_ident,
+    let name = hattrs.debug.name(this_ty_ident, None);
+    if let Some(span) = hattrs
+        .debug
+        .transparent_variants
+        .span
+        .or(hattrs.debug.variant_name_only.span)
+    {
+        bail!(
+            span,
+            "`transparent_variants` and `variant_name_only` can be specified only for enum"
+        );
+    }
+    let options = DebugOptions {
+        rename_all: hattrs.debug.rename_all(),
+        transparent: false,
+        redact: hattrs.debug.is_redact(false),
+        non_exhaustive: hattrs
+            .debug
+            .non_exhaustive()
+            .or(has_non_exhaustive(&item.attrs).then_some(true)),
+    };
+    let (mut expr, fields_body) = build_debug_expr(
+        &name,
         &item.fields,
         fields,
+        options,
         use_bounds,
         to_expr,
         &mut wcb,
     )?;
     let wheres = wcb.build(|ty| quote!(#ty : #trait_));
-    Ok(quote! {
+    let mut ts = TokenStream::new();
+    if let Some(span) = hattrs.debug.flattenable.span {
+        let Fields::Named(_) = &item.fields else {
+            bail!(
+                span,
+                "`flattenable` can be specified only for struct with named fields"
+            );
+        };
+        let Some(fields_body) = fields_body else {
+            bail!(span, "`flattenable` cannot be specified with `transparent`");
+        };
+        // Used by `#[debug(flatten)]` of other types.
+        let vis = &item.vis;
+        let helper = debug_fields_trait_ident(this_ty_ident);
+        ts.extend(quote! {
+            #[doc(hidden)]
+            #vis trait #helper {
+                fn __derive_ex_debug_fields(
+                    &self,
+                    __derive_ex_debug: &mut ::core::fmt::DebugStruct<'_, '_>,
+                ) -> bool;
+            }
+            #[automatically_derived]
+            impl #impl_g #helper for #this_ty #wheres {
+                fn __derive_ex_debug_fields(
+                    &self,
+                    __derive_ex_debug: &mut ::core::fmt::DebugStruct<'_, '_>,
+                ) -> bool #fields_body
+            }
+        });
+        expr = quote! {{
+            let __derive_ex_debug = &mut f.debug_struct(#name);
+            if <Self as #helper>::__derive_ex_debug_fields(self, __derive_ex_debug) {
+                __derive_ex_debug.finish_non_exhaustive()
+            } else {
+                __derive_ex_debug.finish()
+            }
+        }};
+    }
+    ts.extend(quote! {
         #[automatically_derived]
         impl #impl_g #trait_ for #this_ty #wheres {
             fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
                 #expr
             }
         }
-    })
+    });
+    Ok(ts)
+}
+fn debug_fields_trait_ident(ident: &Ident) -> Ident {
+    format_ident!("__DeriveExDebugFields{}", ident.unraw())
+}
+fn debug_fields_trait_path(ty: &Type) -> Result<Path> {
+    if let Type::Path(TypePath { qself: None, path }) = ty {
+        let mut path = path.clone();
+        if let Some(s) = path.segments.last_mut() {
+            s.ident = debug_fields_trait_ident(&s.ident);
+            s.arguments = PathArguments::None;
+            return Ok(path);
+        }
+    }
+    bail!(
+        ty.span(),
+        "`flatten` can be specified only for fields of a struct type with `#[debug(flattenable)]`"
+    )
 }
 fn build_debug_for_enum(
     item: &ItemEnum,
@@ -523,20 +1109,79 @@ fn build_debug_for_enum(
     let this_ty: Type = parse_quote!(#this_ty_ident #type_g);
     let trait_ = kind.to_path();
 
+    if let Some(rename) = &hattrs.debug.rename {
+        bail!(
+            rename.name_span,
+            "`rename = ...` cannot be specified for enum"
+        );
+    }
+    if let Some(span) = hattrs.debug.flattenable.span {
+        bail!(span, "`flattenable` can be specified only for struct");
+    }
+    if e.display {
+        hattrs.debug.verify_display()?;
+        for variant in variants {
+            variant.hattrs.debug.verify_display()?;
+            for field in &variant.fields {
+                field.hattrs.debug.verify_display()?;
+            }
+        }
+        return Ok(build_debug_by_display(&item.generics, &this_ty, e, hattrs));
+    }
     let mut wcb = WhereClauseBuilder::new(&item.generics);
     let use_bounds = e.push_bounds_to_with(hattrs, kind, &mut wcb);
     let mut arms = Vec::new();
     for variant in variants {
         let variant_ident = &variant.variant.ident;
+        let name = variant
+            .hattrs
+            .debug
+            .name(variant_ident, hattrs.debug.rename_all());
         let use_bounds = variant.hattrs.push_bounds_to(use_bounds, kind, &mut wcb);
         let to_expr = |field: &FieldEntry| {
             let var = field.make_ident("");
             quote_spanned!(field.span() => #var)
         };
-        let expr = build_debug_expr(
-            variant_ident,
+        if hattrs.debug.variant_name_only.value() {
+            let pat = variant.make_pat("");
+            arms.push(quote!(#pat => f.write_str(#name)));
+            continue;
+        }
+        let field_count = variant
+            .fields
+            .iter()
+            .filter(|field| !field.hattrs.is_debug_skip())
+            .count();
+        let transparent = if let Some(span) = variant.hattrs.debug.transparent.span {
+            if field_count != 1 {
+                bail!(
+                    span,
+                    "`transparent` can be specified only for variants with a single non-skipped field"
+                );
+            }
+            true
+        } else {
+            hattrs.debug.transparent_variants.value() && field_count == 1
+        };
+        let options = DebugOptions {
+            rename_all: None,
+            transparent,
+            redact: variant
+                .hattrs
+                .debug
+                .is_redact(hattrs.debug.is_redact(false)),
+            non_exhaustive: variant
+                .hattrs
+                .debug
+                .non_exhaustive()
+                .or(hattrs.debug.non_exhaustive())
+                .or(has_non_exhaustive(&variant.variant.attrs).then_some(true)),
+        };
+        let (expr, _) = build_debug_expr(
+            &name,
             &variant.variant.fields,
             &variant.fields,
+            options,
             use_bounds,
             to_expr,
             &mut wcb,
@@ -556,55 +1201,240 @@ fn build_debug_for_enum(
         }
     })
 }
+fn build_debug_for_union(
+    item: &ItemUnion,
+    e: &DeriveEntry,
+    hattrs: &HelperAttributes,
+) -> Result<TokenStream> {
+    let kind = DeriveItemKind::Debug;
+    let (impl_g, type_g, _) = item.generics.split_for_impl();
+    let this_ty_ident = &item.ident;
+    let this_ty: Type = parse_quote!(#this_ty_ident #type_g);
+    let trait_ = kind.to_path();
+
+    if e.display {
+        hattrs.debug.verify_display()?;
+        return Ok(build_debug_by_display(&item.generics, &this_ty, e, hattrs));
+    }
+    let mut wcb = WhereClauseBuilder::new(&item.generics);
+    let _ = e.push_bounds_to_with(hattrs, kind, &mut wcb);
+    let expr = if let Some(with) = &hattrs.debug.with {
+        let with = &with.value;
+        quote_spanned!(with.span()=> { let __derive_ex_with = #with; __derive_ex_with(self, f) })
+    } else {
+        let name = hattrs.debug.name(this_ty_ident, None);
+        quote!(f.debug_struct(#name).finish_non_exhaustive())
+    };
+    let wheres = wcb.build(|ty| quote!(#ty : #trait_));
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_g #trait_ for #this_ty #wheres {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                #expr
+            }
+        }
+    })
+}
+
+fn build_debug_by_display(
+    generics: &Generics,
+    this_ty: &Type,
+    e: &DeriveEntry,
+    hattrs: &HelperAttributes,
+) -> TokenStream {
+    let kind = DeriveItemKind::Debug;
+    let (impl_g, _, _) = generics.split_for_impl();
+    let trait_ = kind.to_path();
+    let mut wcb = WhereClauseBuilder::new(generics);
+    if e.push_bounds_to_with(hattrs, kind, &mut wcb) {
+        wcb.push_bounds_for_type_with(this_ty, &parse_quote!(::core::fmt::Display));
+    }
+    let wheres = wcb.build(|ty| quote!(#ty : #trait_));
+    quote! {
+        #[automatically_derived]
+        impl #impl_g #trait_ for #this_ty #wheres {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(self, f)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct DebugOptions {
+    rename_all: Option<RenameRule>,
+    transparent: bool,
+    redact: bool,
+    non_exhaustive: Option<bool>,
+}
+
 fn build_debug_expr(
-    ident: &Ident,
+    name: &str,
     fields_source: &Fields,
     fields: &[FieldEntry],
+    options: DebugOptions,
     use_bounds: bool,
     to_expr: impl Fn(&FieldEntry) -> TokenStream,
     wcb: &mut WhereClauseBuilder,
-) -> Result<TokenStream> {
+) -> Result<(TokenStream, Option<TokenStream>)> {
     let kind = DeriveItemKind::Debug;
     let mut transparent_field = None;
+    if options.transparent {
+        transparent_field = fields.iter().find(|field| !field.hattrs.is_debug_skip());
+    }
     for field in fields {
         if let Some(span) = field.hattrs.debug.transparent.span {
-            if transparent_field.is_some() {
+            if transparent_field.is_some() && !options.transparent {
                 bail!(span, "only one field can be set `#[debug(transparent)]`");
             }
             transparent_field = Some(field);
         }
     }
-    let expr = if let Some(field) = transparent_field {
+    if let Some(field) = transparent_field {
         let e = to_expr(field);
-        field.push_bounds_to(use_bounds, kind, wcb);
-        quote_spanned!(field.span() => ::core::fmt::Debug::fmt(#e, f))
+        field.hattrs.debug.verify_inherited_redact(options.redact)?;
+        let custom_fmt = field.hattrs.debug.custom_fmt(&e, options.redact);
+        if field.hattrs.push_bounds_to(use_bounds, kind, wcb) {
+            field
+                .hattrs
+                .debug
+                .push_bounds_for_field(field.field, options.redact, wcb);
+        }
+        let expr = custom_fmt
+            .unwrap_or_else(|| quote_spanned!(field.span() => ::core::fmt::Debug::fmt(#e, f)));
+        Ok((expr, None))
     } else {
         let is_named = match fields_source {
             Fields::Named(_) => true,
             Fields::Unnamed(_) | Fields::Unit => false,
         };
-        let mut expr = TokenStream::new();
         let debug_x = match is_named {
             true => quote!(debug_struct),
             false => quote!(debug_tuple),
         };
-        expr.extend(quote!(f.#debug_x(::core::stringify!(#ident))));
+        // The `..` of flattened fields is propagated unless `non_exhaustive = ...` is specified.
+        let collect_non_exhaustive = options.non_exhaustive.is_none()
+            && fields
+                .iter()
+                .any(|field| !field.hattrs.is_debug_skip() && field.hattrs.debug.flatten.value());
+        let mut stmts = TokenStream::new();
+        let mut use_debug_fn = false;
         for field in fields {
             if !field.hattrs.is_debug_skip() {
-                let e = to_expr(field);
-                let member = field.member();
+                let mut e = to_expr(field);
                 let span = field.span();
-                expr.extend(match is_named {
-                    true => quote_spanned!(span => .field(::core::stringify!(#member), #e)),
-                    false => quote_spanned!(span => .field(#e)),
-                });
-                field.push_bounds_to(use_bounds, kind, wcb);
+                field.hattrs.debug.verify_inherited_redact(options.redact)?;
+                let custom_fmt = field.hattrs.debug.custom_fmt(&e, options.redact);
+                let flatten_trait = if field.hattrs.debug.flatten.value() {
+                    Some(debug_fields_trait_path(&field.field.ty)?)
+                } else {
+                    None
+                };
+                if field.hattrs.push_bounds_to(use_bounds, kind, wcb) {
+                    if let Some(flatten_trait) = &flatten_trait {
+                        wcb.push_bounds_for_type_with(&field.field.ty, flatten_trait);
+                    } else {
+                        field
+                            .hattrs
+                            .debug
+                            .push_bounds_for_field(field.field, options.redact, wcb);
+                    }
+                }
+                let skip_if = field.hattrs.debug.skip_if(&e);
+                if let Some(custom_fmt) = custom_fmt {
+                    e = quote_spanned!(span => &__derive_ex_debug_fn(|f: &mut ::core::fmt::Formatter| #custom_fmt));
+                    use_debug_fn = true;
+                }
+                let mut stmt = if let Some(flatten_trait) = &flatten_trait {
+                    if field.field.ident.is_none() {
+                        bail!(span, "`flatten` cannot be specified for unnamed field");
+                    }
+                    let ty = &field.field.ty;
+                    let call = quote_spanned!(span => <#ty as #flatten_trait>::__derive_ex_debug_fields(#e, __derive_ex_debug));
+                    if collect_non_exhaustive {
+                        quote_spanned!(span => __derive_ex_non_exhaustive |= #call;)
+                    } else {
+                        quote_spanned!(span => #call;)
+                    }
+                } else if let Some(ident) = &field.field.ident {
+                    let name = field.hattrs.debug.name(ident, options.rename_all);
+                    quote_spanned!(span => __derive_ex_debug.field(#name, #e);)
+                } else {
+                    if let Some(rename) = &field.hattrs.debug.rename {
+                        bail!(
+                            rename.name_span,
+                            "`rename = ...` cannot be specified for unnamed field"
+                        );
+                    }
+                    quote_spanned!(span => __derive_ex_debug.field(#e);)
+                };
+                if let Some(skip_if) = skip_if {
+                    stmt = quote_spanned!(span => if !#skip_if { #stmt });
+                }
+                stmts.extend(stmt);
             }
         }
-        expr.extend(quote!(.finish()));
-        expr
-    };
-    Ok(expr)
+        // Like `#[derive(Debug)]`, unit structs and variants are printed as the bare name.
+        let non_exhaustive = !matches!(fields_source, Fields::Unit)
+            && options
+                .non_exhaustive
+                .unwrap_or_else(|| fields.iter().any(|field| field.hattrs.is_debug_skip()));
+        let (non_exhaustive_decl, non_exhaustive_value) = if collect_non_exhaustive {
+            (
+                quote!(let mut __derive_ex_non_exhaustive = #non_exhaustive;),
+                quote!(__derive_ex_non_exhaustive),
+            )
+        } else {
+            (quote!(), quote!(#non_exhaustive))
+        };
+        let finish = match (non_exhaustive, is_named) {
+            _ if collect_non_exhaustive => quote! {
+                if __derive_ex_non_exhaustive {
+                    __derive_ex_debug.finish_non_exhaustive()
+                } else {
+                    __derive_ex_debug.finish()
+                }
+            },
+            (false, _) => quote!(__derive_ex_debug.finish()),
+            (true, true) => quote!(__derive_ex_debug.finish_non_exhaustive()),
+            // `DebugTuple::finish_non_exhaustive` is not available in older Rust.
+            // Unlike it, `{:#?}` prints a trailing comma after `..` like other fields.
+            (true, false) => quote! {
+                __derive_ex_debug.field(&::core::format_args!(".."));
+                __derive_ex_debug.finish()
+            },
+        };
+        if use_debug_fn {
+            stmts = quote! {
+                struct __DeriveExDebugFn<F>(F);
+                impl<F: Fn(&mut ::core::fmt::Formatter) -> ::core::fmt::Result> ::core::fmt::Debug
+                    for __DeriveExDebugFn<F>
+                {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        (self.0)(f)
+                    }
+                }
+                fn __derive_ex_debug_fn<F: Fn(&mut ::core::fmt::Formatter) -> ::core::fmt::Result>(
+                    f: F,
+                ) -> __DeriveExDebugFn<F> {
+                    __DeriveExDebugFn(f)
+                }
+                #stmts
+            };
+        }
+        let expr = quote! {{
+            let __derive_ex_debug = &mut f.#debug_x(#name);
+            #non_exhaustive_decl
+            { #stmts }
+            #finish
+        }};
+        let fields_body = quote! {{
+            #non_exhaustive_decl
+            { #stmts }
+            #non_exhaustive_value
+        }};
+        Ok((expr, Some(fields_body)))
+    }
 }
 
 fn build_default_for_struct(
@@ -792,6 +1622,13 @@ fn with_ref(source: &impl ToTokens, is_ref: bool) -> TokenStream {
         quote!(#source)
     }
 }
+fn clone_with(source: &impl ToTokens, ty: &Type, is_clone: bool) -> TokenStream {
+    if is_clone {
+        quote!(<#ty as ::core::clone::Clone>::clone(&#source))
+    } else {
+        quote!(#source)
+    }
+}
 fn build_ctor_args(fields: &Fields, values: &[impl ToTokens]) -> TokenStream {
     match fields {
         Fields::Named(fields) => {
@@ -807,39 +1644,6 @@ fn member(this: TokenStream, field: &FieldEntry) -> TokenStream {
     quote!(#this.#member)
 }
 
-#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
-enum UnaryOp {
-    Neg,
-    Not,
-}
-
-impl UnaryOp {
-    fn from_str(s: &str) -> Option<Self> {
-        Some(match s {
-            "Neg" => Self::Neg,
-            "Not" => Self::Not,
-            _ => return None,
-        })
-    }
-    fn to_str(self) -> &'static str {
-        match self {
-            UnaryOp::Neg => "Neg",
-            UnaryOp::Not => "Not",
-        }
-    }
-    fn to_func_name(self) -> &'static str {
-        match self {
-            UnaryOp::Neg => "neg",
-            UnaryOp::Not => "not",
-        }
-    }
-}
-impl std::fmt::Display for UnaryOp {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.to_str())
-    }
-}
-
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
 enum CompareOp {
     Ord,
@@ -919,6 +1723,7 @@ enum DeriveItemKind {
     BinaryOp(BinaryOp),
     AssignOp(BinaryOp),
     UnaryOp(UnaryOp),
+    OverflowOp(OverflowMode, BinaryOp),
     CompareOp(CompareOp),
     Copy,
     Clone,
@@ -946,6 +1751,12 @@ impl DeriveItemKind {
         if let Some(value) = UnaryOp::from_str(s) {
             return Some(Self::UnaryOp(value));
         }
+        for &mode in OverflowMode::VARIANTS {
+            if let Some(s) = s.strip_prefix(mode.to_str()) {
+                let op = BinaryOp::from_str(s)?;
+                return mode.is_supported(op).then_some(Self::OverflowOp(mode, op));
+            }
+        }
         if let Some(value) = CompareOp::from_str(s) {
             return Some(Self::CompareOp(value));
         }
@@ -976,6 +1787,40 @@ impl DeriveItemKind {
         }
     }
 
+    fn from_attr_name(s: &str) -> Option<Self> {
+        if let Some(s) = s.strip_suffix("_assign") {
+            return Some(Self::AssignOp(BinaryOp::from_str_snake_case(s)?));
+        }
+        if let Some(value) = BinaryOp::from_str_snake_case(s) {
+            return Some(Self::BinaryOp(value));
+        }
+        if let Some(value) = UnaryOp::from_str_snake_case(s) {
+            return Some(Self::UnaryOp(value));
+        }
+        None
+    }
+    fn to_attr_name(self) -> String {
+        match self {
+            DeriveItemKind::BinaryOp(op) => op.to_str_snake_case().to_string(),
+            DeriveItemKind::AssignOp(op) => format!("{}_assign", op.to_str_snake_case()),
+            DeriveItemKind::UnaryOp(op) => op.to_str_snake_case().to_string(),
+            _ => unreachable!(),
+        }
+    }
+    fn ref_kinds(self) -> &'static [RefKind] {
+        match self {
+            DeriveItemKind::BinaryOp(_) => &[
+                RefKind::Owned,
+                RefKind::LhsRef,
+                RefKind::RhsRef,
+                RefKind::BothRef,
+            ],
+            DeriveItemKind::AssignOp(_) => &[RefKind::Owned, RefKind::RhsRef],
+            DeriveItemKind::UnaryOp(_) => &[RefKind::Owned, RefKind::LhsRef],
+            _ => &[],
+        }
+    }
+
     fn to_path(self) -> Path {
         match self {
             DeriveItemKind::BinaryOp(op) => {
@@ -990,6 +1835,7 @@ impl DeriveItemKind {
                 let ident = format_ident!("{}", op.to_str());
                 parse_quote!(::core::ops::#ident)
             }
+            DeriveItemKind::OverflowOp(..) => unreachable!(),
             DeriveItemKind::CompareOp(op) => op.to_path(),
             DeriveItemKind::Copy => parse_quote!(::core::marker::Copy),
             DeriveItemKind::Clone => parse_quote!(::core::clone::Clone),
@@ -1006,6 +1852,7 @@ impl std::fmt::Display for DeriveItemKind {
             DeriveItemKind::BinaryOp(op) => write!(f, "{op}"),
             DeriveItemKind::AssignOp(op) => write!(f, "{op}Assign"),
             DeriveItemKind::UnaryOp(op) => write!(f, "{op}"),
+            DeriveItemKind::OverflowOp(mode, op) => write!(f, "{mode}{op}"),
             DeriveItemKind::CompareOp(op) => write!(f, "{op}"),
             DeriveItemKind::Copy => write!(f, "Copy"),
             DeriveItemKind::Clone => write!(f, "Clone"),
@@ -1017,10 +1864,76 @@ impl std::fmt::Display for DeriveItemKind {
     }
 }
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum RefKind {
+    Owned,
+    LhsRef,
+    RhsRef,
+    BothRef,
+}
+
+impl RefKind {
+    fn new(lhs_is_ref: bool, rhs_is_ref: bool) -> Self {
+        match (lhs_is_ref, rhs_is_ref) {
+            (false, false) => Self::Owned,
+            (true, false) => Self::LhsRef,
+            (false, true) => Self::RhsRef,
+            (true, true) => Self::BothRef,
+        }
+    }
+    fn from_ident(s: &Ident) -> Result<Self> {
+        Ok(match s.to_string().as_str() {
+            "owned" => Self::Owned,
+            "lhs_ref" => Self::LhsRef,
+            "rhs_ref" => Self::RhsRef,
+            "both_ref" => Self::BothRef,
+            _ => bail!(
+                s.span(),
+                "expected `owned`, `lhs_ref`, `rhs_ref` or `both_ref`"
+            ),
+        })
+    }
+    fn from_args(
+        args: &NameValue<ArgsForRefs>,
+        kind: DeriveItemKind,
+        is_common: bool,
+    ) -> Result<Vec<Self>> {
+        if kind.ref_kinds().is_empty() {
+            if is_common {
+                return Ok(Vec::new());
+            }
+            bail!(
+                args.name_span,
+                "`refs = ...` cannot be used with `#[derive_ex({kind})]`"
+            );
+        }
+        let mut refs = Vec::new();
+        for i in &args.value.0 {
+            let r = Self::from_ident(i)?;
+            if !kind.ref_kinds().contains(&r) {
+                if is_common {
+                    continue;
+                }
+                bail!(i.span(), "`{i}` cannot be used with `#[derive_ex({kind})]`");
+            }
+            refs.push(r);
+        }
+        if refs.is_empty() {
+            bail!(
+                args.name_span,
+                "`refs = ...` leaves no implementation of `{kind}` to generate"
+            );
+        }
+        Ok(refs)
+    }
+}
+
 struct DeriveEntry {
     kind: DeriveItemKind,
     span: Span,
     dump: bool,
+    display: bool,
+    refs: Option<Vec<RefKind>>,
     bounds_this: Bounds,
     bounds_common: Bounds,
 }
@@ -1033,20 +1946,69 @@ impl DeriveEntry {
         args_list.extend(parse_derive_ex_attrs(attrs)?);
         Self::from_args_list(&args_list)
     }
+    fn from_mod(
+        mod_attr: &TokenStream,
+        attrs: &[Attribute],
+        filter: impl Fn(DeriveItemKind) -> bool,
+    ) -> Result<Vec<Self>> {
+        let mut args_list: Vec<Args> = parse_derive_ex_attrs(attrs)?;
+        let mut skip = false;
+        for a in &mut args_list {
+            skip |= a.skip.value();
+            a.skip.span = None;
+        }
+        let es = Self::from_args_list(&args_list)?;
+        let mut results = Vec::new();
+        if !skip {
+            for e in Self::from_args_list(&[parse2(mod_attr.clone())?])? {
+                if filter(e.kind) && es.iter().all(|x| x.kind != e.kind) {
+                    results.push(e);
+                }
+            }
+        }
+        results.extend(es);
+        Ok(results)
+    }
     fn from_args_list(args_list: &[Args]) -> Result<Vec<Self>> {
         let mut results = Vec::new();
         for a in args_list {
+            if let Some(span) = a.skip.span {
+                bail!(
+                    span,
+                    "`skip` can be used only for items in `mod` with `#[derive_ex]`"
+                );
+            }
             for item in &a.items {
-                let (dump, bounds_this) =
+                let kind = DeriveItemKind::from_ident(&item.trait_ident)?;
+                let (dump, display, bounds_this, refs_this) =
                     if let DeriveItemArgsOption::Some { args, .. } = &item.args {
-                        (args.dump, Bounds::from(&args.bound))
+                        if let Some(span) = args.display.span {
+                            if kind != DeriveItemKind::Debug {
+                                bail!(span, "`display` can be specified only for `Debug`");
+                            }
+                        }
+                        (
+                            args.dump,
+                            args.display.value(),
+                            Bounds::from(&args.bound),
+                            args.refs.as_ref(),
+                        )
                     } else {
-                        (false, Bounds::new())
+                        (false, false, Bounds::new(), None)
                     };
+                let refs = if let Some(refs) = refs_this {
+                    Some(RefKind::from_args(refs, kind, false)?)
+                } else if let Some(refs) = &a.refs {
+                    Some(RefKind::from_args(refs, kind, true)?)
+                } else {
+                    None
+                };
                 results.push(Self {
-                    kind: DeriveItemKind::from_ident(&item.trait_ident)?,
+                    kind,
                     span: item.trait_ident.span(),
                     dump: a.dump | dump,
+                    display,
+                    refs,
                     bounds_this,
                     bounds_common: Bounds::from(&a.bound),
                 });
@@ -1055,6 +2017,13 @@ impl DeriveEntry {
         Ok(results)
     }
 
+    fn use_refs(&self, r: RefKind) -> bool {
+        match &self.refs {
+            Some(refs) => refs.contains(&r),
+            None => true,
+        }
+    }
+
     fn push_bounds_to(&self, wcb: &mut WhereClauseBuilder) -> bool {
         let mut use_bounds = wcb.push_bounds(&self.bounds_this);
         if use_bounds {
@@ -1188,7 +2157,7 @@ impl<'a> FieldEntry<'a> {
     }
 }
 
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Clone)]
 struct HelperAttributeKinds {
     derive_ex: bool,
     default: bool,
@@ -1198,6 +2167,7 @@ struct HelperAttributeKinds {
     eq: bool,
     partial_eq: bool,
     hash: bool,
+    ops: HashSet<DeriveItemKind>,
 }
 
 impl HelperAttributeKinds {
@@ -1219,6 +2189,13 @@ impl HelperAttributeKinds {
                     CompareOp::PartialEq => self.partial_eq = true,
                     CompareOp::Hash => self.hash = true,
                 },
+                DeriveItemKind::BinaryOp(_) | DeriveItemKind::UnaryOp(_) => {
+                    self.ops.insert(e.kind);
+                }
+                DeriveItemKind::AssignOp(op) => {
+                    self.ops.insert(e.kind);
+                    self.ops.insert(DeriveItemKind::BinaryOp(op));
+                }
                 _ => {}
             }
         }
@@ -1253,14 +2230,16 @@ impl HelperAttributeKinds {
             "eq" => self.is_match_cmp_attr(CompareOp::Eq),
             "partial_eq" => self.is_match_cmp_attr(CompareOp::PartialEq),
             "hash" => self.is_match_cmp_attr(CompareOp::Hash),
-            _ => false,
+            s => {
+                matches!(DeriveItemKind::from_attr_name(s), Some(kind) if self.ops.contains(&kind))
+            }
         }
     }
 
     fn without_derive_ex(&self) -> HelperAttributeKinds {
         HelperAttributeKinds {
             derive_ex: false,
-            ..*self
+            ..self.clone()
         }
     }
 }
@@ -1270,6 +2249,7 @@ struct HelperAttributes {
     default: Option<HelperAttributeForDefault>,
     debug: HelperAttributeForDebug,
     cmp: HelperAttributesForCompareOp,
+    ops: HelperAttributesForOp,
 }
 
 impl HelperAttributes {
@@ -1297,11 +2277,13 @@ impl HelperAttributes {
             HelperAttributeForDebug::default()
         };
         let cmp = HelperAttributesForCompareOp::from_attrs(attrs, kinds)?;
+        let ops = HelperAttributesForOp::from_attrs(attrs, kinds)?;
         let this = Self {
             items,
             default,
             debug,
             cmp,
+            ops,
         };
         this.verify(target)?;
         Ok(this)
@@ -1338,6 +2320,9 @@ impl HelperAttributes {
             match kind {
                 DeriveItemKind::CompareOp(op) => use_bounds = self.cmp.push_bounds(op, wcb),
                 DeriveItemKind::Debug => use_bounds = wcb.push_bounds(&self.debug.bounds),
+                DeriveItemKind::BinaryOp(_)
+                | DeriveItemKind::AssignOp(_)
+                | DeriveItemKind::UnaryOp(_) => use_bounds = self.ops.push_bounds(kind, wcb),
                 DeriveItemKind::Default => {
                     if let Some(a) = &self.default {
                         use_bounds = wcb.push_bounds(&a.bounds)
@@ -1362,7 +2347,9 @@ impl HelperAttributes {
     }
 
     fn verify(&self, target: AttributeTarget) -> Result<()> {
+        self.debug.verify(target)?;
         self.cmp.verify(target)?;
+        self.ops.verify(target)?;
         Ok(())
     }
 }
@@ -1372,6 +2359,19 @@ struct ArgsForDebug {
     transparent: Flag,
     skip: Flag,
     ignore: Flag,
+    with: Option<NameValue<Expr>>,
+    format: Option<NameValue<LitStr>>,
+    skip_if: Option<NameValue<Expr>>,
+    limit: Option<NameValue<LitInt>>,
+    display: Flag,
+    redact: Option<NameValue<Option<LitBool>>>,
+    flatten: Flag,
+    flattenable: Flag,
+    transparent_variants: Flag,
+    variant_name_only: Flag,
+    non_exhaustive: Option<NameValue<LitBool>>,
+    rename: Option<NameValue<LitStr>>,
+    rename_all: Option<NameValue<LitStr>>,
     bound: Option<NameArgs<Vec<Bound>>>,
 }
 
@@ -1379,6 +2379,19 @@ struct ArgsForDebug {
 struct HelperAttributeForDebug {
     transparent: Flag,
     skip: Flag,
+    with: Option<NameValue<Expr>>,
+    format: Option<NameValue<LitStr>>,
+    skip_if: Option<NameValue<Expr>>,
+    limit: Option<(Span, usize)>,
+    display: Flag,
+    redact: Option<(Span, bool)>,
+    flatten: Flag,
+    flattenable: Flag,
+    transparent_variants: Flag,
+    variant_name_only: Flag,
+    non_exhaustive: Option<NameValue<LitBool>>,
+    rename: Option<NameValue<LitStr>>,
+    rename_all: Option<(Span, RenameRule)>,
     bounds: Bounds,
 }
 impl HelperAttributeForDebug {
@@ -1391,12 +2404,321 @@ impl HelperAttributeForDebug {
                 } else {
                     Flag::NONE
                 },
+                with: args.with,
+                format: args.format,
+                skip_if: args.skip_if,
+                limit: if let Some(limit) = &args.limit {
+                    Some((limit.name_span, limit.value.base10_parse()?))
+                } else {
+                    None
+                },
+                display: args.display,
+                redact: args.redact.map(|redact| {
+                    (
+                        redact.name_span,
+                        !matches!(redact.value, Some(LitBool { value: false, .. })),
+                    )
+                }),
+                flatten: args.flatten,
+                flattenable: args.flattenable,
+                transparent_variants: args.transparent_variants,
+                variant_name_only: args.variant_name_only,
+                non_exhaustive: args.non_exhaustive,
+                rename: args.rename,
+                rename_all: if let Some(rename_all) = &args.rename_all {
+                    Some((
+                        rename_all.name_span,
+                        RenameRule::from_lit_str(&rename_all.value)?,
+                    ))
+                } else {
+                    None
+                },
                 bounds: Bounds::from(&args.bound),
             })
         } else {
             Ok(Self::default())
         }
     }
+    fn name(&self, ident: &Ident, rename_all: Option<RenameRule>) -> String {
+        if let Some(rename) = &self.rename {
+            return rename.value.value();
+        }
+        let name = ident.unraw().to_string();
+        if let Some(rename_all) = rename_all {
+            rename_all.apply(&name)
+        } else {
+            name
+        }
+    }
+    fn rename_all(&self) -> Option<RenameRule> {
+        self.rename_all.map(|(_, rule)| rule)
+    }
+    fn non_exhaustive(&self) -> Option<bool> {
+        Some(self.non_exhaustive.as_ref()?.value.value)
+    }
+    fn is_redact(&self, default: bool) -> bool {
+        self.redact.map_or(default, |(_, redact)| redact)
+    }
+    fn custom_fmt(&self, e: &TokenStream, redact: bool) -> Option<TokenStream> {
+        if self.is_redact(redact) {
+            Some(quote!(f.write_str("***")))
+        } else if let Some(with) = &self.with {
+            let with = &with.value;
+            Some(
+                quote_spanned!(with.span()=> { let __derive_ex_with = #with; __derive_ex_with(#e, f) }),
+            )
+        } else if let Some(format) = &self.format {
+            let format = &format.value;
+            Some(quote_spanned!(format.span()=> ::core::write!(f, #format, #e)))
+        } else if let Some(span) = self.display.span {
+            Some(quote_spanned!(span=> ::core::fmt::Display::fmt(#e, f)))
+        } else if let Some((span, limit)) = self.limit {
+            Some(quote_spanned! {span=>
+                {
+                    let mut __derive_ex_iter = ::core::iter::IntoIterator::into_iter(#e);
+                    let mut __derive_ex_list = f.debug_list();
+                    __derive_ex_list.entries(
+                        ::core::iter::Iterator::by_ref(&mut __derive_ex_iter).take(#limit),
+                    );
+                    // Avoid walking the rest of the iterator; the count is shown only when it is exact.
+                    match ::core::iter::Iterator::size_hint(&__derive_ex_iter) {
+                        (0, ::core::option::Option::Some(0)) => {}
+                        (__derive_ex_rest, ::core::option::Option::Some(__derive_ex_max))
+                            if __derive_ex_rest == __derive_ex_max =>
+                        {
+                            __derive_ex_list
+                                .entry(&::core::format_args!("... ({} more)", __derive_ex_rest));
+                        }
+                        _ => {
+                            if ::core::iter::Iterator::next(&mut __derive_ex_iter).is_some() {
+                                __derive_ex_list.entry(&::core::format_args!("..."));
+                            }
+                        }
+                    }
+                    __derive_ex_list.finish()
+                }
+            })
+        } else {
+            None
+        }
+    }
+    fn push_bounds_for_field(&self, field: &Field, redact: bool, wcb: &mut WhereClauseBuilder) {
+        if self.is_redact(redact) || self.with.is_some() || self.format.is_some() {
+            return;
+        }
+        if self.display.value() {
+            wcb.push_bounds_for_field_with(field, &parse_quote!(::core::fmt::Display));
+        } else if self.limit.is_some() {
+            let ty = &field.ty;
+            wcb.push_preds_for_type(
+                ty,
+                [
+                    parse_quote!(for<'a> &'a #ty : ::core::iter::IntoIterator),
+                    parse_quote!(for<'a> <&'a #ty as ::core::iter::IntoIterator>::Item : ::core::fmt::Debug),
+                ],
+            );
+        } else {
+            wcb.push_bounds_for_field(field);
+        }
+    }
+    fn skip_if(&self, e: &TokenStream) -> Option<TokenStream> {
+        let skip_if = &self.skip_if.as_ref()?.value;
+        Some(
+            quote_spanned!(skip_if.span()=> { let __derive_ex_skip_if = #skip_if; __derive_ex_skip_if(#e) }),
+        )
+    }
+    fn fmts(&self) -> Vec<(&'static str, Span)> {
+        let mut fmts = Vec::new();
+        if let Some(with) = &self.with {
+            fmts.push(("`with = ...`", with.name_span));
+        }
+        if let Some(format) = &self.format {
+            fmts.push(("`format = ...`", format.name_span));
+        }
+        if let Some((span, _)) = self.limit {
+            fmts.push(("`limit = ...`", span));
+        }
+        if let Some(span) = self.display.span {
+            fmts.push(("`display`", span));
+        }
+        fmts
+    }
+    // With `Debug(display)`, only `bound(...)` is meaningful.
+    fn verify_display(&self) -> Result<()> {
+        let spans = [
+            ("`transparent`", self.transparent.span),
+            ("`skip`", self.skip.span),
+            ("`with = ...`", self.with.as_ref().map(|a| a.name_span)),
+            ("`format = ...`", self.format.as_ref().map(|a| a.name_span)),
+            (
+                "`skip_if = ...`",
+                self.skip_if.as_ref().map(|a| a.name_span),
+            ),
+            ("`limit = ...`", self.limit.map(|(span, _)| span)),
+            ("`display`", self.display.span),
+            ("`redact`", self.redact.map(|(span, _)| span)),
+            ("`flatten`", self.flatten.span),
+            ("`flattenable`", self.flattenable.span),
+            ("`transparent_variants`", self.transparent_variants.span),
+            ("`variant_name_only`", self.variant_name_only.span),
+            (
+                "`non_exhaustive = ...`",
+                self.non_exhaustive.as_ref().map(|a| a.name_span),
+            ),
+            ("`rename = ...`", self.rename.as_ref().map(|a| a.name_span)),
+            ("`rename_all = ...`", self.rename_all.map(|(span, _)| span)),
+        ];
+        for (name, span) in spans {
+            if let Some(span) = span {
+                bail!(span, "{name} cannot be specified with `Debug(display)`");
+            }
+        }
+        Ok(())
+    }
+    fn verify_inherited_redact(&self, redact: bool) -> Result<()> {
+        if redact && self.redact.is_none() {
+            let mut fmts = self.fmts();
+            if let Some(span) = self.flatten.span {
+                fmts.push(("`flatten`", span));
+            }
+            if let Some((name, span)) = fmts.first() {
+                bail!(
+                    *span,
+                    "{name} cannot be specified for a field redacted by the type or variant (specify `redact = false` for the field to use it)"
+                );
+            }
+        }
+        Ok(())
+    }
+    fn verify(&self, target: AttributeTarget) -> Result<()> {
+        let mut fmts = self.fmts();
+        if let Some((span, true)) = self.redact {
+            fmts.push(("`redact`", span));
+        }
+        if let [(name0, _), (name1, span), ..] = fmts[..] {
+            bail!(
+                span,
+                "{name0} and {name1} cannot be specified at the same time"
+            );
+        }
+        if target != AttributeTarget::Field {
+            if let Some(span) = self.display.span {
+                bail!(span, "`display` can be specified only for fields");
+            }
+            if let Some(with) = &self.with {
+                if target != AttributeTarget::Union {
+                    bail!(
+                        with.name_span,
+                        "`with = ...` can be specified only for fields and unions"
+                    );
+                }
+            }
+            if let Some(format) = &self.format {
+                bail!(
+                    format.name_span,
+                    "`format = ...` can be specified only for fields"
+                );
+            }
+            if let Some(skip_if) = &self.skip_if {
+                bail!(
+                    skip_if.name_span,
+                    "`skip_if = ...` can be specified only for fields"
+                );
+            }
+        }
+        if let Some((span, _)) = self.limit {
+            if target != AttributeTarget::Field {
+                bail!(span, "`limit = ...` can be specified only for fields");
+            }
+        }
+        if let Some(span) = self.flattenable.span {
+            if target != AttributeTarget::Type {
+                bail!(span, "`flattenable` can be specified only for struct");
+            }
+        }
+        if target == AttributeTarget::Type {
+            if let Some(span) = self.transparent.span {
+                bail!(
+                    span,
+                    "`transparent` can be specified only for variants and fields"
+                );
+            }
+        }
+        if target != AttributeTarget::Type {
+            if let Some(span) = self.transparent_variants.span {
+                bail!(
+                    span,
+                    "`transparent_variants` can be specified only for enum"
+                );
+            }
+            if let Some(span) = self.variant_name_only.span {
+                bail!(span, "`variant_name_only` can be specified only for enum");
+            }
+        }
+        if let (Some(span), Some(_)) = (self.transparent_variants.span, self.variant_name_only.span)
+        {
+            bail!(
+                span,
+                "`transparent_variants` and `variant_name_only` cannot be specified at the same time"
+            );
+        }
+        if let Some(span) = self.flatten.span {
+            if target != AttributeTarget::Field {
+                bail!(span, "`flatten` can be specified only for fields");
+            }
+            if let Some((name, _)) = fmts.first() {
+                bail!(
+                    span,
+                    "`flatten` and {name} cannot be specified at the same time"
+                );
+            }
+            if self.transparent.value()
+                || matches!(self.redact, Some((_, true)))
+                || self.rename.is_some()
+            {
+                bail!(
+                    span,
+                    "`flatten` cannot be specified with `transparent`, `redact` or `rename = ...`"
+                );
+            }
+        }
+        if let (Some(skip_if), Some(_)) = (&self.skip_if, self.transparent.span) {
+            bail!(
+                skip_if.name_span,
+                "`skip_if = ...` cannot be specified with `transparent`"
+            );
+        }
+        if target == AttributeTarget::Union {
+            let spans = [
+                ("`transparent`", self.transparent.span),
+                ("`redact`", self.redact.map(|(span, _)| span)),
+                (
+                    "`non_exhaustive = ...`",
+                    self.non_exhaustive.as_ref().map(|a| a.name_span),
+                ),
+                ("`rename_all = ...`", self.rename_all.map(|(span, _)| span)),
+            ];
+            for (name, span) in spans {
+                if let Some(span) = span {
+                    bail!(span, "{name} cannot be specified for union");
+                }
+            }
+        }
+        if target == AttributeTarget::Field {
+            if let Some(non_exhaustive) = &self.non_exhaustive {
+                bail!(
+                    non_exhaustive.name_span,
+                    "`non_exhaustive = ...` cannot be specified for fields"
+                );
+            }
+        }
+        if target != AttributeTarget::Type {
+            if let Some((span, _)) = &self.rename_all {
+                bail!(*span, "`rename_all = ...` can be specified only for types");
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(StructMeta, Debug)]
@@ -1468,6 +2790,104 @@ struct ArgsForCompareOp {
     bound: Option<NameArgs<Vec<Bound>>>,
 }
 
+#[derive(StructMeta, Default, Debug)]
+struct ArgsForOp {
+    by: Option<NameValue<Expr>>,
+    to: Option<NameValue<Ident>>,
+    bound: Option<NameArgs<Vec<Bound>>>,
+}
+
+#[derive(Default)]
+struct HelperAttributesForOp {
+    items: HashMap<DeriveItemKind, HelperAttributeForOp>,
+}
+
+impl HelperAttributesForOp {
+    fn from_attrs(attrs: &[Attribute], kinds: &HelperAttributeKinds) -> Result<Self> {
+        let mut items = HashMap::new();
+        for &kind in &kinds.ops {
+            if let Some(args) = parse_single::<ArgsForOp>(attrs, &kind.to_attr_name())? {
+                items.insert(
+                    kind,
+                    HelperAttributeForOp {
+                        by: args.by.map(|x| x.value),
+                        to: args.to.map(|x| x.value),
+                        bounds: Bounds::from(&args.bound),
+                    },
+                );
+            }
+        }
+        Ok(Self { items })
+    }
+
+    // `#[add_assign(...)]` takes precedence over `#[add(...)]` when deriving `AddAssign`.
+    fn sources(kind: DeriveItemKind) -> Vec<DeriveItemKind> {
+        match kind {
+            DeriveItemKind::AssignOp(op) => vec![kind, DeriveItemKind::BinaryOp(op)],
+            _ => vec![kind],
+        }
+    }
+    fn push_bounds(&self, kind: DeriveItemKind, wcb: &mut WhereClauseBuilder) -> bool {
+        let mut use_bounds = true;
+        for source in Self::sources(kind) {
+            if let Some(a) = self.items.get(&source) {
+                if use_bounds {
+                    use_bounds = wcb.push_bounds(&a.bounds);
+                }
+            }
+        }
+        use_bounds
+    }
+    fn by(&self, kind: DeriveItemKind) -> Option<OpBy<'_>> {
+        for source in Self::sources(kind) {
+            if let Some(by) = self.items.get(&source).and_then(|a| a.by.as_ref()) {
+                return Some(OpBy {
+                    kind: source,
+                    expr: by,
+                });
+            }
+        }
+        None
+    }
+    fn to(&self, kind: DeriveItemKind) -> Option<&Ident> {
+        self.items.get(&kind)?.to.as_ref()
+    }
+    fn verify(&self, target: AttributeTarget) -> Result<()> {
+        for (kind, a) in &self.items {
+            if let Some(by) = &a.by {
+                if target != AttributeTarget::Field {
+                    bail!(by.span(), "`by = ...` can be specified only for fields");
+                }
+            }
+            if let Some(to) = &a.to {
+                if !matches!(kind, DeriveItemKind::UnaryOp(_)) {
+                    bail!(
+                        to.span(),
+                        "`to = ...` cannot be specified for `#[{}]`",
+                        kind.to_attr_name()
+                    );
+                }
+                if target != AttributeTarget::Variant {
+                    bail!(to.span(), "`to = ...` can be specified only for variants");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct HelperAttributeForOp {
+    by: Option<Expr>,
+    to: Option<Ident>,
+    bounds: Bounds,
+}
+
+struct OpBy<'a> {
+    kind: DeriveItemKind,
+    expr: &'a Expr,
+}
+
 fn remove_attrs(attrs: &mut Vec<Attribute>, kinds: &HelperAttributeKinds) {
     attrs.retain(|attr| !kinds.is_match(attr));
 }
@@ -1481,6 +2901,11 @@ fn parse_derive_ex_attrs<T: Parse>(attrs: &[Attribute]) -> Result<Vec<T>> {
     }
     Ok(items)
 }
+fn has_non_exhaustive(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("non_exhaustive"))
+}
 fn parse_single<T: Parse + Default>(attrs: &[Attribute], name: &str) -> Result<Option<T>> {
     let mut item = None;
     for attr in attrs {
@@ -1505,6 +2930,7 @@ fn parse_single<T: Parse + Default>(attrs: &[Attribute], name: &str) -> Result<O
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum AttributeTarget {
     Type,
+    Union,
     Variant,
     Field,
 }
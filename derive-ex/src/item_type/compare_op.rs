@@ -986,7 +986,7 @@ impl HelperAttributeForCompareOp {
 
     fn verify(&self, target: AttributeTarget) -> Result<()> {
         match target {
-            AttributeTarget::Type => {
+            AttributeTarget::Type | AttributeTarget::Union => {
                 if let Some(by) = &self.by {
                     bail!(by.span(), "cannot specify `by = ...` for type");
                 }
@@ -0,0 +1,102 @@
+use syn::{LitStr, Result};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(super) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    const VARIANTS: &'static [(&'static str, Self)] = &[
+        ("lowercase", Self::Lower),
+        ("UPPERCASE", Self::Upper),
+        ("PascalCase", Self::Pascal),
+        ("camelCase", Self::Camel),
+        ("snake_case", Self::Snake),
+        ("SCREAMING_SNAKE_CASE", Self::ScreamingSnake),
+        ("kebab-case", Self::Kebab),
+        ("SCREAMING-KEBAB-CASE", Self::ScreamingKebab),
+    ];
+
+    pub fn from_lit_str(s: &LitStr) -> Result<Self> {
+        let value = s.value();
+        for &(name, rule) in Self::VARIANTS {
+            if name == value {
+                return Ok(rule);
+            }
+        }
+        let names: Vec<_> = Self::VARIANTS
+            .iter()
+            .map(|(name, _)| format!("\"{name}\""))
+            .collect();
+        bail!(
+            s.span(),
+            "unknown rename rule `{value}`, expected one of {}",
+            names.join(", ")
+        )
+    }
+
+    pub fn apply(self, name: &str) -> String {
+        let words = split_words(name);
+        match self {
+            Self::Lower => name.to_lowercase(),
+            Self::Upper => name.to_uppercase(),
+            Self::Pascal => words.iter().map(|w| capitalize(w)).collect(),
+            Self::Camel => {
+                let mut s = String::new();
+                for (i, w) in words.iter().enumerate() {
+                    if i == 0 {
+                        s.push_str(&w.to_lowercase());
+                    } else {
+                        s.push_str(&capitalize(w));
+                    }
+                }
+                s
+            }
+            Self::Snake => words.join("_").to_lowercase(),
+            Self::ScreamingSnake => words.join("_").to_uppercase(),
+            Self::Kebab => words.join("-").to_lowercase(),
+            Self::ScreamingKebab => words.join("-").to_uppercase(),
+        }
+    }
+}
+
+fn split_words(s: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    for part in s.split('_').filter(|s| !s.is_empty()) {
+        let cs: Vec<_> = part.char_indices().collect();
+        let mut start = 0;
+        for i in 1..cs.len() {
+            let (index, c) = cs[i];
+            let prev = cs[i - 1].1;
+            let next_is_lower = cs.get(i + 1).is_some_and(|&(_, c)| c.is_lowercase());
+            if c.is_uppercase()
+                && (prev.is_lowercase()
+                    || prev.is_numeric()
+                    || (prev.is_uppercase() && next_is_lower))
+            {
+                words.push(&part[start..index]);
+                start = index;
+            }
+        }
+        words.push(&part[start..]);
+    }
+    words
+}
+
+fn capitalize(s: &str) -> String {
+    let mut cs = s.chars();
+    match cs.next() {
+        Some(c) => c
+            .to_uppercase()
+            .chain(cs.flat_map(|c| c.to_lowercase()))
+            .collect(),
+        None => String::new(),
+    }
+}
@@ -22,27 +22,50 @@ use syn::{parse2, Item, Result};
 /// - [Derive `Clone`](#derive-clone)
 /// - [Derive `Debug`](#derive-debug)
 ///   - [`#[debug(skip)]`](#debugskip)
+///   - [`#[debug(skip_if = ...)]`](#debugskip_if--)
+///   - [`#[debug(redact)]`](#debugredact)
+///   - [`#[debug(non_exhaustive = ...)]`](#debugnon_exhaustive--)
 ///   - [`#[debug(transparent)]`](#debugtransparent)
+///   - [`#[debug(flatten)]`, `#[debug(flattenable)]`](#debugflatten-debugflattenable)
+///   - [`#[debug(transparent_variants)]`, `#[debug(variant_name_only)]`](#debugtransparent_variants-debugvariant_name_only)
+///   - [`#[debug(with = ...)]`](#debugwith--)
+///   - [`#[debug(format = ...)]`](#debugformat--)
+///   - [`#[debug(limit = ...)]`](#debuglimit--)
+///   - [`#[debug(display)]`, `#[derive_ex(Debug(display))]`](#debugdisplay-derive_exdebugdisplay)
+///   - [`#[debug(rename = ...)]`, `#[debug(rename_all = ...)]`](#debugrename---debugrename_all--)
 ///   - [`#[debug(bound(...))]`](#debugbound)
+///   - [Derive `Debug` for unions](#derive-debug-for-unions)
+///   - [Derive `Debug` from `impl Display`](#derive-debug-from-impl-display)
 /// - [Derive `Default`](#derive-default)
+/// - [Derive `Default`, `From`, `FromStr` from inherent methods](#derive-default-from-fromstr-from-inherent-methods)
 /// - [Derive `Ord`, `PartialOrd`, `Eq`, `PartialEq`, `Hash`](#derive-ord-partialord-eq-partialeq-hash)
 ///   - [`#[ord(skip)]`](#ordskip)
 ///   - [`#[ord(reverse)]`](#ordreverse)
 ///   - [`#[ord(by = ...)]`](#ordby--)
 ///   - [`#[ord(key = ...)]`](#ordkey--)
 ///   - [`#[ord(bound(...))]`](#ordbound)
+///   - [Derive `PartialOrd`, `PartialEq`, `Eq` from `impl Ord`](#derive-partialord-partialeq-eq-from-impl-ord)
+///   - [Derive `PartialEq` from `impl PartialEq`](#derive-partialeq-from-impl-partialeq)
 /// - [Derive `Deref`](#derive-deref)
+///   - [Derive `AsRef`, `Borrow` from `impl Deref`](#derive-asref-borrow-from-impl-deref)
 /// - [Derive `DerefMut`](#derive-derefmut)
 /// - [Derive operators](#derive-operators)
 ///   - [`Add`-like](#add-like)
 ///     - [Derive `Add` from struct definition](#derive-add-from-struct-definition)
+///     - [`#[derive_ex(Add(refs = ...))]`](#derive_exaddrefs--)
 ///     - [Derive `Add` from `impl Add`](#derive-add-from-impl-add)
 ///     - [Derive `Add` from `impl AddAssign`](#derive-add-from-impl-addassign)
+///     - [Derive `Sub` from `impl Add`](#derive-sub-from-impl-add)
 ///   - [`AddAssign`-like](#addassign-like)
 ///     - [Derive `AddAssign` from struct definition](#derive-addassign-from-struct-definition)
 ///     - [Derive `AddAssign` from `impl Add`](#derive-addassign-from-impl-add)
 ///     - [Derive both `Add` and `AddAssign` from `impl Add`](#derive-both-add-and-addassign-from-impl-add)
+///     - [Derive `AddAssign` from `impl AddAssign`](#derive-addassign-from-impl-addassign)
 ///   - [`Not`-like](#not-like)
+///     - [Derive `Not` from `impl Not`](#derive-not-from-impl-not)
+///   - [`#[add(by = ...)]`](#addby--)
+///   - [`CheckedAdd`-like](#checkedadd-like)
+/// - [Apply to items in a module](#apply-to-items-in-a-module)
 /// - [Specify trait bound](#specify-trait-bound)
 ///   - [`#[bound(T)]`](#boundt)
 ///   - [`#[bound(T : TraitName)]`](#boundt--traitname)
@@ -54,23 +77,32 @@ use syn::{parse2, Item, Result};
 ///
 /// You can write attributes in the following positions.
 ///
-/// | attribute                  | impl | struct | enum | variant | field |
-/// | -------------------------- | ---- | ------ | ---- | ------- | ----- |
-/// | `#[derive_ex(Copy)]`       |      | ✔      | ✔    | ✔       | ✔     |
-/// | `#[derive_ex(Clone)]`      |      | ✔      | ✔    | ✔       | ✔     |
-/// | `#[derive_ex(Debug)]`      |      | ✔      | ✔    | ✔       | ✔     |
-/// | `#[derive_ex(Default)]`    |      | ✔      | ✔    | ✔       | ✔     |
-/// | `#[derive_ex(Ord)]`        |      | ✔      | ✔    | ✔       | ✔     |
-/// | `#[derive_ex(Deref)]`      |      | ✔      |      |         |       |
-/// | `#[derive_ex(DerefMut)]`   |      | ✔      |      |         |       |
-/// | `#[derive_ex(Add)]`        | ✔    | ✔      |      |         | ✔     |
-/// | `#[derive_ex(AddAssign)]`  | ✔    | ✔      |      |         | ✔     |
-/// | `#[derive_ex(Not)]`        |      | ✔      | ✔    | ✔       | ✔     |
-/// | `#[derive_ex(bound(...))]` |      | ✔      | ✔    | ✔       | ✔     |
-/// | `#[derive_ex(dump))]`      | ✔    | ✔      | ✔    |         |       |
-/// | `#[default]`               |      | ✔      | ✔    | ✔       | ✔     |
-/// | `#[debug]`                 |      | ✔      | ✔    | ✔       | ✔     |
-/// | `#[ord]`                   |      | ✔      | ✔    | ✔       | ✔     |
+/// | attribute                  | impl | mod | struct | enum | union | variant | field |
+/// | -------------------------- | ---- | --- | ------ | ---- | ----- | ------- | ----- |
+/// | `#[derive_ex(Copy)]`       |      | ✔   | ✔      | ✔    |       | ✔       | ✔     |
+/// | `#[derive_ex(Clone)]`      |      | ✔   | ✔      | ✔    |       | ✔       | ✔     |
+/// | `#[derive_ex(Debug)]`      | ✔    | ✔   | ✔      | ✔    | ✔     | ✔       | ✔     |
+/// | `#[derive_ex(Default)]`    | ✔    | ✔   | ✔      | ✔    |       | ✔       | ✔     |
+/// | `#[derive_ex(From)]`       | ✔    |     |        |      |       |         |       |
+/// | `#[derive_ex(FromStr)]`    | ✔    |     |        |      |       |         |       |
+/// | `#[derive_ex(Ord)]`        | ✔    | ✔   | ✔      | ✔    |       | ✔       | ✔     |
+/// | `#[derive_ex(Deref)]`      |      | ✔   | ✔      |      |       |         |       |
+/// | `#[derive_ex(DerefMut)]`   |      | ✔   | ✔      |      |       |         |       |
+/// | `#[derive_ex(AsRef)]`      | ✔    |     |        |      |       |         |       |
+/// | `#[derive_ex(Borrow)]`     | ✔    |     |        |      |       |         |       |
+/// | `#[derive_ex(Add)]`        | ✔    | ✔   | ✔      |      |       |         | ✔     |
+/// | `#[derive_ex(AddAssign)]`  | ✔    | ✔   | ✔      |      |       |         | ✔     |
+/// | `#[derive_ex(Not)]`        | ✔    | ✔   | ✔      | ✔    |       | ✔       | ✔     |
+/// | `#[derive_ex(CheckedAdd)]` |      | ✔   | ✔      |      |       |         | ✔     |
+/// | `#[derive_ex(bound(...))]` |      | ✔   | ✔      | ✔    |       | ✔       | ✔     |
+/// | `#[derive_ex(refs = ...)]` |      | ✔   | ✔      |      |       |         |       |
+/// | `#[derive_ex(skip)]`       |      |     | ✔      | ✔    | ✔     |         |       |
+/// | `#[derive_ex(dump))]`      | ✔    | ✔   | ✔      | ✔    | ✔     |         |       |
+/// | `#[default]`               |      |     | ✔      | ✔    |       | ✔       | ✔     |
+/// | `#[debug]`                 |      |     | ✔      | ✔    | ✔     | ✔       | ✔     |
+/// | `#[ord]`                   |      |     | ✔      | ✔    |       | ✔       | ✔     |
+/// | `#[add]`                   |      |     | ✔      |      |       |         | ✔     |
+/// | `#[neg]`, `#[not]`         |      |     | ✔      | ✔    |       | ✔       | ✔     |
 ///
 /// # Derive `Copy`
 ///
@@ -210,15 +242,29 @@ use syn::{parse2, Item, Result};
 ///
 /// The following helper attribute arguments allow you to customize your `Debug` implementation.
 ///
-/// | attribute                          | struct | enum | variant | field |
-/// | ---------------------------------- | ------ | ---- | ------- | ----- |
-/// | [`skip`](#debugskip)               |        |      |         | ✔     |
-/// | [`transparent`](#debugtransparent) |        |      |         | ✔     |
-/// | [`bound(...)`](#debugbound)        | ✔      | ✔    | ✔       | ✔     |
+/// | attribute                                                                   | struct | enum | union | variant | field |
+/// | --------------------------------------------------------------------------- | ------ | ---- | ----- | ------- | ----- |
+/// | [`skip`](#debugskip)                                                        |        |      |       |         | ✔     |
+/// | [`skip_if = ...`](#debugskip_if--)                                          |        |      |       |         | ✔     |
+/// | [`redact`](#debugredact)                                                    | ✔      | ✔    |       | ✔       | ✔     |
+/// | [`non_exhaustive = ...`](#debugnon_exhaustive--)                            | ✔      | ✔    |       | ✔       |       |
+/// | [`transparent`](#debugtransparent)                                          |        |      |       | ✔       | ✔     |
+/// | [`flatten`](#debugflatten-debugflattenable)                                 |        |      |       |         | ✔     |
+/// | [`flattenable`](#debugflatten-debugflattenable)                             | ✔      |      |       |         |       |
+/// | [`transparent_variants`](#debugtransparent_variants-debugvariant_name_only) |        | ✔    |       |         |       |
+/// | [`variant_name_only`](#debugtransparent_variants-debugvariant_name_only)    |        | ✔    |       |         |       |
+/// | [`with = ...`](#debugwith--)                                                |        |      | ✔     |         | ✔     |
+/// | [`format = ...`](#debugformat--)                                            |        |      |       |         | ✔     |
+/// | [`limit = ...`](#debuglimit--)                                              |        |      |       |         | ✔     |
+/// | [`display`](#debugdisplay-derive_exdebugdisplay)                            |        |      |       |         | ✔     |
+/// | [`rename = ...`](#debugrename---debugrename_all--)                          | ✔      |      | ✔     | ✔       | ✔     |
+/// | [`rename_all = ...`](#debugrename---debugrename_all--)                      | ✔      | ✔    |       |         |       |
+/// | [`bound(...)`](#debugbound)                                                 | ✔      | ✔    | ✔     | ✔       | ✔     |
 ///
 /// ## `#[debug(skip)]`
 ///
 /// By setting `#[debug(skip)]` to a field, you can exclude that field from debug output.
+/// In that case, `..` is output to indicate that there are omitted fields. (See [`#[debug(non_exhaustive = ...)]`](#debugnon_exhaustive--))
 ///
 /// For backward compatibility, `#[debug(ignore)]` is also supported and works the same as `skip`.
 ///
@@ -230,7 +276,84 @@ use syn::{parse2, Item, Result};
 ///     #[debug(skip)]
 ///     b: u32,
 /// }
-/// assert_eq!(format!("{:?}", X { a: 1, b: 2 }), "X { a: 1 }");
+/// assert_eq!(format!("{:?}", X { a: 1, b: 2 }), "X { a: 1, .. }");
+/// ```
+///
+/// ## `#[debug(skip_if = ...)]`
+///
+/// By setting `#[debug(skip_if = ...)]` to a field, you can exclude that field from debug output only when the specified function of type `fn(&T) -> bool` returns `true`.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+/// #[derive_ex(Debug)]
+/// struct X {
+///     a: u32,
+///     #[debug(skip_if = Option::is_none)]
+///     b: Option<u32>,
+///     #[debug(skip_if = Vec::is_empty)]
+///     c: Vec<u32>,
+/// }
+/// assert_eq!(format!("{:?}", X { a: 1, b: None, c: vec![] }), "X { a: 1 }");
+/// assert_eq!(
+///     format!("{:?}", X { a: 1, b: Some(2), c: vec![] }),
+///     "X { a: 1, b: Some(2) }"
+/// );
+/// ```
+///
+/// ## `#[debug(redact)]`
+///
+/// By setting `#[debug(redact)]` to a field, `***` is output instead of the value of that field.
+/// Unlike `#[debug(skip)]`, the field name remains in debug output.
+/// No `Debug` constraint is set for that field.
+///
+/// By setting `#[debug(redact)]` to a struct, enum or variant, all fields in it are redacted.
+/// You can exclude fields from redaction with `#[debug(redact = false)]`.
+/// Fields redacted in this way cannot have `with = ...`, `format = ...`, `limit = ...`, `display` or `flatten`.
+/// To format such a field in a custom way, also specify `#[debug(redact = false)]` for it.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+/// #[derive_ex(Debug)]
+/// #[debug(redact)]
+/// struct X {
+///     #[debug(redact = false)]
+///     user: String,
+///     password: String,
+/// }
+/// assert_eq!(
+///     format!("{:?}", X { user: "alice".into(), password: "secret".into() }),
+///     r#"X { user: "alice", password: *** }"#
+/// );
+/// ```
+///
+/// ## `#[debug(non_exhaustive = ...)]`
+///
+/// If a field has `#[debug(skip)]`, or a struct or variant has `#[non_exhaustive]`, the debug output ends with `..` like `DebugStruct::finish_non_exhaustive`.
+///
+/// You can control this behavior with `#[debug(non_exhaustive = true)]` or `#[debug(non_exhaustive = false)]`.
+/// When set to an enum, it applies to all variants.
+///
+/// Note that this differs from `#[derive(Debug)]`, which ignores `#[non_exhaustive]`.
+/// Unit structs and unit variants are always printed as the bare name, like `#[derive(Debug)]`.
+/// For tuple structs and tuple variants, `..` is output as the last field, so `{:#?}` prints it with a trailing comma like the other fields (`X(\n    1,\n    ..,\n)`), while named structs print `..` without a comma.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+/// #[derive_ex(Debug)]
+/// #[non_exhaustive]
+/// struct X {
+///     a: u32,
+/// }
+/// assert_eq!(format!("{:?}", X { a: 1 }), "X { a: 1, .. }");
+///
+/// #[derive_ex(Debug)]
+/// #[debug(non_exhaustive = false)]
+/// struct Y {
+///     a: u32,
+///     #[debug(skip)]
+///     b: u32,
+/// }
+/// assert_eq!(format!("{:?}", Y { a: 1, b: 2 }), "Y { a: 1 }");
 /// ```
 ///
 /// ## `#[debug(transparent)]`
@@ -248,6 +371,250 @@ use syn::{parse2, Item, Result};
 /// assert_eq!(format!("{:?}", X { a: 1, b: 2 }), "2");
 /// ```
 ///
+/// By setting `#[debug(transparent)]` to a variant with a single field, you can transfer processing to that field.
+/// Fields with `#[debug(skip)]` are not counted.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+/// #[derive_ex(Debug)]
+/// enum Value {
+///     #[debug(transparent)]
+///     Int(i32),
+///     Str(String),
+/// }
+/// assert_eq!(format!("{:?}", Value::Int(3)), "3");
+/// assert_eq!(format!("{:?}", Value::Str("a".into())), r#"Str("a")"#);
+/// ```
+///
+/// ## `#[debug(flatten)]`, `#[debug(flattenable)]`
+///
+/// By setting `#[debug(flatten)]` to a named field, the fields of that field are output inline instead of as a nested struct.
+///
+/// The type of the field must be a struct with named fields that has `#[derive_ex(Debug)]` and `#[debug(flattenable)]`.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+/// #[derive_ex(Debug)]
+/// #[debug(flattenable)]
+/// struct Inner {
+///     b: u32,
+///     c: u32,
+/// }
+///
+/// #[derive_ex(Debug)]
+/// struct X {
+///     a: u32,
+///     #[debug(flatten)]
+///     inner: Inner,
+/// }
+/// let x = X { a: 1, inner: Inner { b: 2, c: 3 } };
+/// assert_eq!(format!("{:?}", x), "X { a: 1, b: 2, c: 3 }");
+/// ```
+///
+/// To flatten a field of a type with [`#[debug(redact)]`](#debugredact), specify `redact = false` for the field.
+///
+/// `#[debug(flattenable)]` implements a hidden helper trait named `__DeriveExDebugFields{type name}` next to the type.
+/// The constraints of the flattened field are placed on this trait, so no additional [`#[debug(bound(...))]`](#debugbound) is required for generic types.
+/// The helper trait is looked up at the same path as the type of the field, so if the type is imported with `use`, the helper trait must also be imported.
+///
+/// ```rust
+/// mod m {
+///     use derive_ex::derive_ex;
+///     #[derive_ex(Debug)]
+///     #[debug(flattenable)]
+///     pub struct Inner {
+///         pub b: u32,
+///     }
+/// }
+/// use derive_ex::derive_ex;
+/// use m::{Inner, __DeriveExDebugFieldsInner};
+///
+/// #[derive_ex(Debug)]
+/// struct X {
+///     #[debug(flatten)]
+///     inner: Inner,
+/// }
+/// ```
+///
+/// If the flattened value would end with `..`, such as when it has a field with `#[debug(skip)]`, the outer value also ends with `..`.
+/// This can be overridden with [`#[debug(non_exhaustive = ...)]`](#debugnon_exhaustive--) on the outer struct or variant.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+/// #[derive_ex(Debug)]
+/// #[debug(flattenable)]
+/// struct Inner {
+///     b: u32,
+///     #[debug(skip)]
+///     c: u32,
+/// }
+///
+/// #[derive_ex(Debug)]
+/// struct X {
+///     a: u32,
+///     #[debug(flatten)]
+///     inner: Inner,
+/// }
+/// let x = X { a: 1, inner: Inner { b: 2, c: 3 } };
+/// assert_eq!(format!("{:?}", x), "X { a: 1, b: 2, .. }");
+/// ```
+///
+/// ## `#[debug(transparent_variants)]`, `#[debug(variant_name_only)]`
+///
+/// By setting `#[debug(transparent_variants)]` to an enum, all variants with a single field are output as that field, in the same way as `#[debug(transparent)]` for variants.
+///
+/// By setting `#[debug(variant_name_only)]` to an enum, only the names of the variants are output.
+/// In this case, no `Debug` constraint is set for the fields.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+/// #[derive_ex(Debug)]
+/// #[debug(transparent_variants)]
+/// enum Value {
+///     Int(i32),
+///     Pair(i32, i32),
+/// }
+/// assert_eq!(format!("{:?}", Value::Int(3)), "3");
+/// assert_eq!(format!("{:?}", Value::Pair(1, 2)), "Pair(1, 2)");
+///
+/// #[derive_ex(Debug)]
+/// #[debug(variant_name_only)]
+/// enum State {
+///     Running { pid: u32 },
+///     Stopped,
+/// }
+/// assert_eq!(format!("{:?}", State::Running { pid: 1 }), "Running");
+/// ```
+///
+/// ## `#[debug(with = ...)]`
+///
+/// By setting `#[debug(with = ...)]` to a field, you can format that field with a function of type `fn(&T, &mut Formatter) -> fmt::Result` instead of `Debug`.
+///
+/// The field type is not required to implement `Debug`, so no `Debug` constraint is set for that field.
+/// If the function requires other constraints, use [`#[debug(bound(...))]`](#debugbound).
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+/// use std::fmt::{Formatter, Result};
+///
+/// fn fmt_hex(value: &u32, f: &mut Formatter) -> Result {
+///     write!(f, "0x{value:x}")
+/// }
+///
+/// #[derive_ex(Debug)]
+/// struct X {
+///     a: u32,
+///     #[debug(with = fmt_hex)]
+///     b: u32,
+/// }
+/// assert_eq!(format!("{:?}", X { a: 1, b: 255 }), "X { a: 1, b: 0xff }");
+/// ```
+///
+/// ## `#[debug(format = ...)]`
+///
+/// By setting `#[debug(format = "...")]` to a field, you can format that field with a format string.
+/// The format string receives the reference to the field as its only argument.
+///
+/// As with `with = ...`, no `Debug` constraint is set for that field.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+///
+/// #[derive_ex(Debug)]
+/// struct X {
+///     #[debug(format = "{:#x}")]
+///     a: u32,
+///     #[debug(format = "{}")]
+///     b: String,
+/// }
+/// assert_eq!(
+///     format!("{:?}", X { a: 255, b: "abc".into() }),
+///     "X { a: 0xff, b: abc }"
+/// );
+/// ```
+///
+/// ## `#[debug(limit = ...)]`
+///
+/// By setting `#[debug(limit = N)]` to a field whose reference implements `IntoIterator`, only the first `N` elements are output as a list, followed by the number of omitted elements.
+/// The rest of the iterator is not traversed, so the number is output only if [`Iterator::size_hint`] is exact (as with [`ExactSizeIterator`]); otherwise only `...` is output.
+///
+/// The constraint `for<'a> <&'a T as IntoIterator>::Item: Debug` is set instead of `T: Debug`.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+/// #[derive_ex(Debug)]
+/// struct X {
+///     #[debug(limit = 3)]
+///     a: Vec<u32>,
+/// }
+/// assert_eq!(
+///     format!("{:?}", X { a: (0..100).collect() }),
+///     "X { a: [0, 1, 2, ... (97 more)] }"
+/// );
+/// ```
+///
+/// ## `#[debug(display)]`, `#[derive_ex(Debug(display))]`
+///
+/// By setting `#[debug(display)]` to a field, that field is output using `Display` instead of `Debug`.
+/// The constraint `T: Display` is set instead of `T: Debug`.
+///
+/// By using `#[derive_ex(Debug(display))]` for a struct or enum, `Debug` is implemented by calling `Display` of that type.
+/// The constraint `Self: Display` is set instead of the constraints for the fields.
+/// In this case, `#[debug(...)]` attributes other than [`bound(...)`](#debugbound) cannot be used, because the fields are not output individually.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+/// use std::fmt::{Display, Formatter, Result};
+///
+/// #[derive_ex(Debug)]
+/// struct X {
+///     #[debug(display)]
+///     a: String,
+/// }
+/// assert_eq!(format!("{:?}", X { a: "abc".into() }), "X { a: abc }");
+///
+/// #[derive_ex(Debug(display))]
+/// struct Name(String);
+///
+/// impl Display for Name {
+///     fn fmt(&self, f: &mut Formatter) -> Result {
+///         write!(f, "{}", self.0)
+///     }
+/// }
+/// assert_eq!(format!("{:?}", Name("abc".into())), "abc");
+/// ```
+///
+/// ## `#[debug(rename = ...)]`, `#[debug(rename_all = ...)]`
+///
+/// By setting `#[debug(rename = "...")]` to a struct, variant or named field, you can change the name used in debug output.
+///
+/// By setting `#[debug(rename_all = "...")]` to a struct, you can change the names of all fields.
+/// When set to an enum, the names of all variants are changed.
+/// The following rules are available.
+///
+/// `"lowercase"`, `"UPPERCASE"`, `"PascalCase"`, `"camelCase"`, `"snake_case"`, `"SCREAMING_SNAKE_CASE"`, `"kebab-case"`, `"SCREAMING-KEBAB-CASE"`
+///
+/// These rules are the same as those of serde. `"lowercase"` and `"UPPERCASE"` only change the case, so `user_id` becomes `user_id` and `USER_ID`.
+///
+/// `rename` takes precedence over `rename_all`.
+/// Raw identifiers such as `r#type` are output without the `r#` prefix.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+///
+/// #[derive_ex(Debug)]
+/// #[debug(rename = "Request", rename_all = "camelCase")]
+/// struct X {
+///     user_id: u32,
+///     #[debug(rename = "kind")]
+///     r#type: u32,
+/// }
+/// assert_eq!(
+///     format!("{:?}", X { user_id: 1, r#type: 2 }),
+///     "Request { userId: 1, kind: 2 }"
+/// );
+/// ```
+///
 /// ## `#[debug(bound(...))]`
 ///
 /// The standard `#[derive(Debug)]` sets `Debug` constraint on the generic parameters, while `#[derive_ex(Debug)]` sets `Debug` constraint on the type of field containing generic parameters.
@@ -297,6 +664,75 @@ use syn::{parse2, Item, Result};
 ///
 /// You can also manually specify a trait bound. See [Specify trait bound](#specify-trait-bound) for details.
 ///
+/// ## Derive `Debug` for unions
+///
+/// `#[derive_ex(Debug)]` can also be applied to `union`.
+///
+/// Since the active field of a union is unknown, by default only the type name is output, like `U { .. }`.
+/// To output the contents, use `#[debug(with = ...)]` to specify a function of type `fn(&Self, &mut Formatter) -> fmt::Result` that reads the union in a way that is known to be safe.
+///
+/// `Debug(display)`, `#[debug(rename = ...)]` and `#[debug(bound(...))]` can also be used. `#[debug]` cannot be specified for fields of a union.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+/// use std::fmt::{Formatter, Result};
+///
+/// #[derive_ex(Debug)]
+/// union Opaque {
+///     a: u32,
+///     b: f32,
+/// }
+///
+/// #[derive_ex(Debug)]
+/// #[debug(with = fmt_bits)]
+/// union Bits {
+///     a: u32,
+///     b: f32,
+/// }
+///
+/// fn fmt_bits(x: &Bits, f: &mut Formatter) -> Result {
+///     // Every bit pattern is a valid `u32`.
+///     write!(f, "Bits({:#x})", unsafe { x.a })
+/// }
+///
+/// assert_eq!(format!("{:?}", Opaque { a: 1 }), "Opaque { .. }");
+/// assert_eq!(format!("{:?}", Bits { b: 1.0 }), "Bits(0x3f800000)");
+/// ```
+///
+/// ## Derive `Debug` from `impl Display`
+///
+/// By applying `#[derive_ex(Debug)]` to `impl Display for T`, you can implement `Debug` that outputs the same as `Display`.
+///
+/// By using `Debug(quoted)`, the output of `Display` is quoted and escaped like `str`.
+///
+/// By using `Debug(type_name)`, the quoted output of `Display` is wrapped in the type name.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+/// use std::fmt::{Display, Formatter, Result};
+///
+/// struct Version(u32, u32);
+///
+/// #[derive_ex(Debug)]
+/// impl Display for Version {
+///     fn fmt(&self, f: &mut Formatter) -> Result {
+///         write!(f, "{}.{}", self.0, self.1)
+///     }
+/// }
+///
+/// struct Name(String);
+///
+/// #[derive_ex(Debug(type_name))]
+/// impl Display for Name {
+///     fn fmt(&self, f: &mut Formatter) -> Result {
+///         write!(f, "{}", self.0)
+///     }
+/// }
+///
+/// assert_eq!(format!("{:?}", Version(1, 2)), "1.2");
+/// assert_eq!(format!("{:?}", Name("abc".into())), r#"Name("abc")"#);
+/// ```
+///
 /// # Derive `Default`
 ///
 /// You can use `#[derive_ex(Default)]` to implement [`Default`].
@@ -441,6 +877,39 @@ use syn::{parse2, Item, Result};
 /// assert_eq!(X::default(), X { a: NoDefault })
 /// ```
 ///
+/// # Derive `Default`, `From`, `FromStr` from inherent methods
+///
+/// By applying `#[derive_ex(...)]` to `impl T`, you can implement the following traits by calling the inherent methods.
+///
+/// | attribute               | method                                 | generated implementation            |
+/// | ----------------------- | -------------------------------------- | ----------------------------------- |
+/// | `#[derive_ex(Default)]` | `fn new() -> Self`                     | `impl Default for T`                |
+/// | `#[derive_ex(From)]`    | `fn new(value: A) -> Self`             | `impl From<A> for T`                |
+/// | `#[derive_ex(FromStr)]` | `fn parse(s: &str) -> Result<Self, E>` | `impl FromStr for T` with `Err = E` |
+///
+/// The methods must have exactly the signatures above. Generic parameters, including `impl Trait` arguments, are not allowed.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+/// use std::num::ParseIntError;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct X(u32);
+///
+/// #[derive_ex(Default, FromStr)]
+/// impl X {
+///     fn new() -> Self {
+///         X(10)
+///     }
+///     fn parse(s: &str) -> Result<Self, ParseIntError> {
+///         Ok(X(s.parse()?))
+///     }
+/// }
+///
+/// assert_eq!(X::default(), X(10));
+/// assert_eq!("5".parse::<X>(), Ok(X(5)));
+/// ```
+///
 /// # Derive `Ord`, `PartialOrd`, `Eq`, `PartialEq`, `Hash`
 ///
 /// `Ord`, `PartialOrd`, `Eq`, `PartialEq`, `Hash` can be derived using common settings.
@@ -550,6 +1019,90 @@ use syn::{parse2, Item, Result};
 ///
 /// For details, see [Specify trait bound](#specify-trait-bound).
 ///
+/// ## Derive `PartialOrd`, `PartialEq`, `Eq` from `impl Ord`
+///
+/// By applying `#[derive_ex(PartialOrd, PartialEq, Eq)]` to `impl Ord for T`, you can implement `PartialOrd`, `PartialEq` and `Eq` consistent with `Ord`.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+/// use std::cmp::Ordering;
+///
+/// struct X {
+///     key: u32,
+///     value: String,
+/// }
+///
+/// #[derive_ex(PartialOrd, PartialEq, Eq)]
+/// impl Ord for X {
+///     fn cmp(&self, other: &Self) -> Ordering {
+///         self.key.cmp(&other.key)
+///     }
+/// }
+/// ```
+///
+/// The above code generates the following code.
+///
+/// ```rust
+/// # use std::cmp::Ordering;
+/// #
+/// # struct X {
+/// #     key: u32,
+/// #     value: String,
+/// # }
+/// #
+/// # impl Ord for X {
+/// #     fn cmp(&self, other: &Self) -> Ordering {
+/// #         self.key.cmp(&other.key)
+/// #     }
+/// # }
+/// impl PartialOrd for X {
+///     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+///         Some(self.cmp(other))
+///     }
+/// }
+/// impl PartialEq for X {
+///     fn eq(&self, other: &Self) -> bool {
+///         self.cmp(other) == Ordering::Equal
+///     }
+/// }
+/// impl Eq for X {}
+/// ```
+///
+/// ## Derive `PartialEq` from `impl PartialEq`
+///
+/// By applying `#[derive_ex(PartialEq)]` to `impl PartialEq<B> for A`, you can implement the following.
+///
+/// - `impl PartialEq<&B> for A`
+/// - `impl PartialEq<B> for &A`
+/// - `impl PartialEq<A> for B`
+/// - `impl PartialEq<A> for &B`
+/// - `impl PartialEq<&A> for B`
+/// - `impl PartialEq<String> for A` and `impl PartialEq<A> for String` (only when `B` is `str` and `PartialEq(string)` is used)
+///
+/// If `B` contains generic parameters, only `impl PartialEq<&B> for A` is implemented, because the others may conflict with the implementations in the standard library or violate the orphan rules.
+///
+/// The implementations for `String` require `std`, so they are generated only when `#[derive_ex(PartialEq(string))]` is specified.
+///
+/// `PartialOrd` can be used in the same way by applying `#[derive_ex(PartialOrd)]` to `impl PartialOrd<B> for A`.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+///
+/// struct Name(String);
+///
+/// #[derive_ex(PartialEq(string))]
+/// impl PartialEq<str> for Name {
+///     fn eq(&self, other: &str) -> bool {
+///         self.0 == other
+///     }
+/// }
+///
+/// let name = Name("abc".to_string());
+/// assert!(name == "abc");
+/// assert!("abc" == name);
+/// assert!(name == "abc".to_string());
+/// ```
+///
 /// # Derive `Deref`
 ///
 /// You can use `#[derive(Deref)]` for struct with a single field to implement `Deref`.
@@ -563,6 +1116,51 @@ use syn::{parse2, Item, Result};
 /// let _: &u8 = &X(10u8);
 /// ```
 ///
+/// ## Derive `AsRef`, `Borrow` from `impl Deref`
+///
+/// By applying `#[derive_ex(AsRef, Borrow)]` to `impl Deref for T`, you can implement `AsRef<Target>` and `Borrow<Target>` by calling `Deref::deref`.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+/// use std::ops::Deref;
+///
+/// struct X(String);
+///
+/// #[derive_ex(AsRef, Borrow)]
+/// impl Deref for X {
+///     type Target = str;
+///     fn deref(&self) -> &Self::Target {
+///         &self.0
+///     }
+/// }
+/// ```
+///
+/// The above code generates the following code.
+///
+/// ```rust
+/// # use std::ops::Deref;
+/// # use std::borrow::Borrow;
+/// #
+/// # struct X(String);
+/// #
+/// # impl Deref for X {
+/// #     type Target = str;
+/// #     fn deref(&self) -> &Self::Target {
+/// #         &self.0
+/// #     }
+/// # }
+/// impl AsRef<str> for X {
+///     fn as_ref(&self) -> &str {
+///         self.deref()
+///     }
+/// }
+/// impl Borrow<str> for X {
+///     fn borrow(&self) -> &str {
+///         self.deref()
+///     }
+/// }
+/// ```
+///
 /// # Derive `DerefMut`
 ///
 /// You can use `#[derive(DerefMut)]` for struct with a single field to implement `DerefMut`.
@@ -653,6 +1251,55 @@ use syn::{parse2, Item, Result};
 /// }
 /// ```
 ///
+/// If a field is an array (`[T; N]`) or a tuple, the operator is applied to each element.
+///
+/// In that case, the trait bound is set on the element type (`T : Add<Output = T>`) instead of the array type.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+///
+/// #[derive(PartialEq, Debug)]
+/// #[derive_ex(Add, AddAssign, Neg)]
+/// struct Color {
+///     rgba: [f32; 4],
+/// }
+///
+/// let c = Color { rgba: [0.5, 0.25, 0.0, 1.0] } + Color { rgba: [0.25, 0.5, 1.0, 0.0] };
+/// assert_eq!(c, Color { rgba: [0.75, 0.75, 1.0, 1.0] });
+/// ```
+///
+/// ### `#[derive_ex(Add(refs = ...))]`
+///
+/// By default, implementations for all combinations of `T` and `&T` are generated.
+/// You can limit the generated implementations by using `refs = ...`.
+///
+/// | value      | generated implementation |
+/// | ---------- | ------------------------ |
+/// | `owned`    | `impl Add<T> for T`      |
+/// | `lhs_ref`  | `impl Add<T> for &T`     |
+/// | `rhs_ref`  | `impl Add<&T> for T`     |
+/// | `both_ref` | `impl Add<&T> for &T`    |
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+///
+/// #[derive(Eq, PartialEq, Debug)]
+/// #[derive_ex(Add(refs = owned))]
+/// struct X(u32);
+///
+/// #[derive(Eq, PartialEq, Debug)]
+/// #[derive_ex(Add(refs = [owned, both_ref]))]
+/// struct Y(u32);
+///
+/// assert_eq!(X(1) + X(2), X(3));
+/// assert_eq!(&Y(1) + &Y(2), Y(3));
+/// ```
+///
+/// For `AddAssign`, `owned` and `rhs_ref` can be used. For `Not`, `owned` and `lhs_ref` can be used.
+///
+/// As with `bound(...)`, it can be applied to multiple traits by writing `#[derive_ex(Add, AddAssign, refs = owned)]`.
+/// In that case, values that cannot be used with a trait are ignored for that trait. If no value is left for a trait, it is an error.
+///
 /// ### Derive `Add` from `impl Add`
 ///
 /// By applying `#[derive_ex(Add)]` to one of the following, you can implement the remaining three.
@@ -664,6 +1311,10 @@ use syn::{parse2, Item, Result};
 ///
 /// `T` must implement `Clone`, except when `#[derive_ex(Add)]` is applied to `impl Add<&T> for &T`.
 ///
+/// References may have explicit lifetimes, such as `impl<'a, 'b> Add<&'b T> for &'a T`.
+/// The generated implementations do not reuse those lifetimes, so they are implemented for references of any lifetime.
+/// Therefore, the lifetime of each reference must be a lifetime parameter of the `impl` that is not used elsewhere, such as in the referenced type. `'static` cannot be used.
+///
 /// ```rust
 /// use derive_ex::derive_ex;
 /// use std::ops::Add;
@@ -766,6 +1417,55 @@ use syn::{parse2, Item, Result};
 /// }
 /// ```
 ///
+/// ### Derive `Sub` from `impl Add`
+///
+/// By applying `#[derive_ex(Sub)]` to `impl Add<Rhs> for T`, you can implement `Sub<Rhs> for T` as `self + (-rhs)`.
+/// `Rhs` must implement `Neg<Output = Rhs>`.
+///
+/// Similarly, by applying `#[derive_ex(Div(reciprocal = ...))]` to `impl Mul<Rhs> for T`, you can implement `Div<Rhs> for T` as `self * reciprocal(rhs)`.
+/// The specified function must take `Rhs` and return `Rhs`.
+///
+/// These cannot be used when `Rhs` is a reference.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+/// use std::ops::{Add, Mul, Neg};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct X(f64);
+///
+/// impl X {
+///     fn recip(self) -> X {
+///         X(1.0 / self.0)
+///     }
+/// }
+/// impl Neg for X {
+///     type Output = X;
+///     fn neg(self) -> Self::Output {
+///         X(-self.0)
+///     }
+/// }
+///
+/// #[derive_ex(Sub)]
+/// impl Add for X {
+///     type Output = X;
+///     fn add(self, rhs: X) -> Self::Output {
+///         X(self.0 + rhs.0)
+///     }
+/// }
+///
+/// #[derive_ex(Div(reciprocal = X::recip))]
+/// impl Mul for X {
+///     type Output = X;
+///     fn mul(self, rhs: X) -> Self::Output {
+///         X(self.0 * rhs.0)
+///     }
+/// }
+///
+/// assert_eq!(X(5.0) - X(2.0), X(3.0));
+/// assert_eq!(X(5.0) / X(2.0), X(2.5));
+/// ```
+///
 /// ## `AddAssign`-like
 ///
 /// In this document, `AddAssign` is used as an example, but all of the following traits can be used in the same way.
@@ -821,6 +1521,8 @@ use syn::{parse2, Item, Result};
 /// }
 /// ```
 ///
+/// As with `Add`, array and tuple fields are processed element by element.
+///
 /// ### Derive `AddAssign` from `impl Add`
 ///
 /// By applying `#[derive_ex(AddAssign)]` to `impl Add<Rhs> for T` or `impl Add<Rhs> for &T`, you can implement `AddAssign<Rhs> for T`.
@@ -886,6 +1588,45 @@ use syn::{parse2, Item, Result};
 /// - `impl Add<&T> for T`
 /// - `impl Add<&T> for &T`
 ///
+/// ### Derive `AddAssign` from `impl AddAssign`
+///
+/// By applying `#[derive_ex(AddAssign)]` to `impl AddAssign<T> for U` or `impl AddAssign<&T> for U`, you can implement the other one.
+///
+/// When `#[derive_ex(AddAssign)]` is applied to `impl AddAssign<T> for U`, `T` must implement `Clone`.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+/// use std::ops::AddAssign;
+///
+/// struct X(Vec<u8>);
+///
+/// #[derive_ex(AddAssign)]
+/// impl AddAssign<&X> for X {
+///     fn add_assign(&mut self, rhs: &X) {
+///         self.0.extend_from_slice(&rhs.0);
+///     }
+/// }
+/// ```
+///
+/// The above code generates the following code.
+///
+/// ```rust
+/// # use std::ops::AddAssign;
+/// #
+/// # struct X(Vec<u8>);
+/// #
+/// # impl AddAssign<&X> for X {
+/// #     fn add_assign(&mut self, rhs: &X) {
+/// #         self.0.extend_from_slice(&rhs.0);
+/// #     }
+/// # }
+/// impl AddAssign<X> for X {
+///     fn add_assign(&mut self, rhs: X) {
+///         *self += &rhs;
+///     }
+/// }
+/// ```
+///
 /// ## `Not`-like
 ///
 /// In this document, `Not` is used as an example, but all of the following traits can be used in the same way.
@@ -936,6 +1677,231 @@ use syn::{parse2, Item, Result};
 /// }
 /// ```
 ///
+/// As with `Add`, array and tuple fields are processed element by element.
+///
+/// ### Enums
+///
+/// For enums, each variant is mapped to the same variant with its fields applied the operator.
+///
+/// By setting `#[not(to = ...)]` to a variant without fields, you can map that variant to another variant without fields.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+///
+/// #[derive(Eq, PartialEq, Debug)]
+/// #[derive_ex(Not)]
+/// enum Switch {
+///     #[not(to = Off)]
+///     On,
+///     #[not(to = On)]
+///     Off,
+///     Unknown(bool),
+/// }
+///
+/// assert_eq!(!Switch::On, Switch::Off);
+/// assert_eq!(!Switch::Off, Switch::On);
+/// assert_eq!(!Switch::Unknown(true), Switch::Unknown(false));
+/// ```
+///
+/// ### Derive `Not` from `impl Not`
+///
+/// By applying `#[derive_ex(Not)]` to `impl Not for T` or `impl Not for &T`, you can implement the other one.
+///
+/// When `#[derive_ex(Not)]` is applied to `impl Not for T`, `T` must implement `Clone`.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+/// use std::ops::Not;
+///
+/// #[derive(Clone)]
+/// struct X(bool);
+///
+/// #[derive_ex(Not)]
+/// impl Not for X {
+///     type Output = X;
+///     fn not(self) -> Self::Output {
+///         X(!self.0)
+///     }
+/// }
+/// ```
+///
+/// The above code generates the following code.
+///
+/// ```rust
+/// # use std::ops::Not;
+/// #
+/// # #[derive(Clone)]
+/// # struct X(bool);
+/// #
+/// # impl Not for X {
+/// #     type Output = X;
+/// #     fn not(self) -> Self::Output {
+/// #         X(!self.0)
+/// #     }
+/// # }
+/// impl Not for &X {
+///     type Output = X;
+///     fn not(self) -> Self::Output {
+///         !self.clone()
+///     }
+/// }
+/// ```
+///
+/// ## `#[add(by = ...)]`
+///
+/// By setting `#[add(by = ...)]` to a field, you can use the specified function instead of `Add::add` for that field.
+///
+/// The function must have the same signature as the trait's required method, taking the field values by value.
+/// When deriving the implementation for references, the field values are cloned before being passed to the function.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+///
+/// #[derive(Eq, PartialEq, Debug)]
+/// #[derive_ex(Add, AddAssign)]
+/// struct X {
+///     #[add(by = u8::wrapping_add)]
+///     a: u8,
+///     #[add(by = u8::saturating_add)]
+///     b: u8,
+/// }
+///
+/// assert_eq!(X { a: 200, b: 200 } + X { a: 100, b: 100 }, X { a: 44, b: 255 });
+/// ```
+///
+/// The helper attribute name is the snake case of the trait name. (`#[add]`, `#[bit_and]`, `#[add_assign]`, `#[neg]`, etc.)
+///
+/// `#[add(...)]` is also used when deriving `AddAssign`. In that case, the field value is cloned and reassigned with the result of the function.
+/// To specify a function for `AddAssign` directly, use `#[add_assign(by = ...)]`, which takes precedence over `#[add(by = ...)]`.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+///
+/// #[derive(Eq, PartialEq, Debug)]
+/// #[derive_ex(AddAssign)]
+/// struct X(#[add_assign(by = |l: &mut u8, r| *l = l.wrapping_add(r))] u8);
+///
+/// let mut x = X(200);
+/// x += X(100);
+/// assert_eq!(x, X(44));
+/// ```
+///
+/// Trait bounds can be specified with `#[add(bound(...))]` in the same way as other helper attributes. See [Specify trait bound](#specify-trait-bound) for details.
+///
+/// ## `CheckedAdd`-like
+///
+/// You can use `#[derive_ex(CheckedAdd)]`, `#[derive_ex(WrappingAdd)]` and `#[derive_ex(SaturatingAdd)]` to generate inherent methods that call the method of the same name on each field.
+///
+/// `checked_*` returns `None` if any of the fields returns `None`.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+///
+/// #[derive(Eq, PartialEq, Debug)]
+/// #[derive_ex(CheckedAdd, WrappingAdd, SaturatingAdd)]
+/// struct X {
+///     a: u8,
+///     b: u32,
+/// }
+///
+/// assert_eq!(X { a: 10, b: 20 }.checked_add(X { a: 1, b: 2 }), Some(X { a: 11, b: 22 }));
+/// assert_eq!(X { a: 255, b: 20 }.checked_add(X { a: 1, b: 2 }), None);
+/// assert_eq!(X { a: 255, b: 20 }.wrapping_add(X { a: 1, b: 2 }), X { a: 0, b: 22 });
+/// assert_eq!(X { a: 255, b: 20 }.saturating_add(X { a: 1, b: 2 }), X { a: 255, b: 22 });
+/// ```
+///
+/// The above code generates the following code.
+///
+/// ```rust
+/// # struct X {
+/// #     a: u8,
+/// #     b: u32,
+/// # }
+/// impl X {
+///     fn checked_add(self, rhs: Self) -> Option<Self> {
+///         Some(X {
+///             a: self.a.checked_add(rhs.a)?,
+///             b: self.b.checked_add(rhs.b)?,
+///         })
+///     }
+///     fn wrapping_add(self, rhs: Self) -> Self {
+///         X {
+///             a: self.a.wrapping_add(rhs.a),
+///             b: self.b.wrapping_add(rhs.b),
+///         }
+///     }
+///     fn saturating_add(self, rhs: Self) -> Self {
+///         X {
+///             a: self.a.saturating_add(rhs.a),
+///             b: self.b.saturating_add(rhs.b),
+///         }
+///     }
+/// }
+/// ```
+///
+/// The following methods can be generated.
+///
+/// |              | `Add` | `Sub` | `Mul` | `Div` | `Rem` |
+/// | ------------ | ----- | ----- | ----- | ----- | ----- |
+/// | `Checked`    | ✔     | ✔     | ✔     | ✔     | ✔     |
+/// | `Wrapping`   | ✔     | ✔     | ✔     | ✔     | ✔     |
+/// | `Saturating` | ✔     | ✔     | ✔     | ✔     |       |
+///
+/// Since there is no standard trait for these methods, trait bounds are not generated automatically.
+/// If a field type contains generic parameters, specify a predicate using a trait that provides the method with [`bound(...)`](#specify-trait-bound).
+/// The methods are called as `field.checked_add(rhs.field)`, so the trait must take `self` and the right-hand side by value.
+/// Traits that take them by reference, such as `num_traits::CheckedAdd`, cannot be used.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+///
+/// trait MyCheckedAdd: Sized {
+///     fn checked_add(self, rhs: Self) -> Option<Self>;
+/// }
+/// impl MyCheckedAdd for u8 {
+///     fn checked_add(self, rhs: Self) -> Option<Self> {
+///         u8::checked_add(self, rhs)
+///     }
+/// }
+///
+/// #[derive(Eq, PartialEq, Debug)]
+/// #[derive_ex(CheckedAdd(bound(T : MyCheckedAdd)))]
+/// struct X<T>(T);
+///
+/// assert_eq!(X(1u8).checked_add(X(2u8)), Some(X(3u8)));
+/// assert_eq!(X(255u8).checked_add(X(2u8)), None);
+/// ```
+///
+/// # Apply to items in a module
+///
+/// By applying `#[derive_ex(...)]` to an inline `mod`, you can apply the same `#[derive_ex(...)]` to all structs and enums in that module.
+///
+/// `#[derive_ex(...)]` on each item is applied in addition to the one on the module.
+/// If the same trait is specified for both, the one on the item takes precedence.
+///
+/// By using `#[derive_ex(skip)]`, the item is excluded from `#[derive_ex(...)]` on the module.
+///
+/// Unions in the module receive only `Debug` from `#[derive_ex(...)]` on the module, because the other traits cannot be derived for unions.
+///
+/// ```rust
+/// use derive_ex::derive_ex;
+///
+/// #[derive_ex(Clone, Debug, bound(..))]
+/// mod messages {
+///     pub struct Ping(pub u32);
+///
+///     #[derive_ex(Default)]
+///     pub struct Pong(pub u32);
+///
+///     #[derive_ex(Debug, skip)]
+///     pub struct Secret(pub String);
+/// }
+///
+/// let _ = messages::Ping(1).clone();
+/// let _ = messages::Pong::default().clone();
+/// let _ = format!("{:?}", messages::Secret("xyz".into()));
+/// ```
+///
 /// # Specify trait bound
 ///
 /// If the type definition or impl item to which `#[derive_ex]` is applied has generic parameters, then by default, trait bound required by the auto-generated code is set.
@@ -1176,7 +2142,38 @@ pub fn derive_ex(
 /// ```
 #[proc_macro_derive(
     Ex,
-    attributes(derive_ex, ord, partial_ord, eq, partial_eq, hash, debug, default)
+    attributes(
+        derive_ex,
+        ord,
+        partial_ord,
+        eq,
+        partial_eq,
+        hash,
+        debug,
+        default,
+        add,
+        bit_and,
+        bit_or,
+        bit_xor,
+        div,
+        mul,
+        rem,
+        shl,
+        shr,
+        sub,
+        add_assign,
+        bit_and_assign,
+        bit_or_assign,
+        bit_xor_assign,
+        div_assign,
+        mul_assign,
+        rem_assign,
+        shl_assign,
+        shr_assign,
+        sub_assign,
+        neg,
+        not
+    )
 )]
 pub fn derive_ex_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input: TokenStream = input.into();
@@ -1192,10 +2189,12 @@ fn build(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
     let ts = match &mut item {
         Item::Struct(item_struct) => item_type::build_by_item_struct(attr, item_struct),
         Item::Enum(item_enum) => item_type::build_by_item_enum(attr, item_enum),
+        Item::Union(item_union) => item_type::build_by_item_union(attr, item_union),
         Item::Impl(item_impl) => item_impl::build_by_item_impl(attr, item_impl),
+        Item::Mod(item_mod) => item_type::build_by_item_mod(attr, item_mod),
         _ => bail!(
             _,
-            "`#[derive_ex]` can be specified only for `struct`, `enum`, or `impl`.",
+            "`#[derive_ex]` can be specified only for `struct`, `enum`, `union`, `impl`, or `mod`.",
         ),
     }
     .unwrap_or_else(|e| e.to_compile_error());